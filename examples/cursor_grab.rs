@@ -26,6 +26,21 @@ fn main() {
                     match key {
                         Escape => return winit::ControlFlow::Break,
                         G => window.grab_cursor(!modifiers.shift).unwrap(),
+                        L => window.set_cursor_grab(if modifiers.shift {
+                            winit::CursorGrabMode::None
+                        } else {
+                            winit::CursorGrabMode::Locked
+                        }).unwrap(),
+                        // Confines the cursor to the middle of the window, and goes back to the grab
+                        // above once the area is cleared.
+                        C => window.confine_cursor_to(if modifiers.shift {
+                            None
+                        } else {
+                            window.get_inner_size().map(|size| (
+                                winit::dpi::LogicalPosition::new(size.width / 4.0, size.height / 4.0),
+                                winit::dpi::LogicalSize::new(size.width / 2.0, size.height / 2.0),
+                            ))
+                        }).unwrap(),
                         H => window.hide_cursor(!modifiers.shift),
                         _ => (),
                     }
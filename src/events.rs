@@ -39,15 +39,64 @@ pub enum WindowEvent {
     /// A file has been dropped into the window.
     DroppedFile(PathBuf),
 
+    /// Data other than files has been dropped into the window.
+    ///
+    /// Only data of the types accepted with `WindowExt::set_accepted_drop_types` is reported,
+    /// which makes this Linux-only.
+    DroppedData {
+        /// The type the data was received as.
+        mime_type: String,
+        data: Vec<u8>,
+    },
+
     /// A file is being hovered over the window.
     HoveredFile(PathBuf),
 
-    /// A file was hovered, but has exited the window.
+    /// A drag started with `WindowExt::start_drag` has ended.
+    DragEnded {
+        /// What the drop target did with the data, or `None` if the drag was cancelled or
+        /// dropped where it wasn't accepted.
+        action: Option<DndAction>,
+    },
+
+    /// The files being hovered over the window have moved.
+    ///
+    /// This is emitted every time the cursor moves during the drag, so the drop target under the
+    /// cursor can be highlighted. Only emitted on X11 and Wayland for now, where
+    /// `WindowExt::set_drop_response` tells the drag source whether the data can be dropped there.
+    DragMoved {
+        /// The position of the cursor in logical coordinates, relative to the top-left corner of
+        /// the window.
+        position: LogicalPosition,
+    },
+
+    /// A file was hovered, but has exited the window, or was dropped where the window rejects it.
     HoveredFileCancelled,
 
     /// The window received a unicode character.
     ReceivedCharacter(char),
 
+    /// The input method's pre-edit text has changed.
+    ///
+    /// `cursor_range` is the byte range of the cursor or selection within `text`, or `None` if the
+    /// cursor should be hidden. An empty `text` means the pre-edit was cleared.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on X11 and Wayland. On X11, the input method must support
+    /// `XIMPreeditCallbacks`, and otherwise draws the pre-edit text itself. On Wayland, the
+    /// compositor must support `zwp_text_input_v3`.
+    ImePreedit { text: String, cursor_range: Option<(usize, usize)> },
+
+    /// The input method has committed a string.
+    ///
+    /// This is followed by a `ReceivedCharacter` event for each of its characters.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on X11 and Wayland.
+    ImeCommit(String),
+
     /// The window gained or lost focus.
     ///
     /// The parameter is true if the window has gained focus, and false if it has lost focus.
@@ -87,6 +136,31 @@ pub enum WindowEvent {
     /// is being pressed) and stage (integer representing the click level).
     TouchpadPressure { device_id: DeviceId, pressure: f32, stage: i64 },
 
+    /// A pinch gesture on a touchpad, for zooming.
+    ///
+    /// `delta` is how much the distance between the fingers changed since the last event, as a
+    /// fraction of the distance when the gesture started. Each gesture starts with a
+    /// `TouchPhase::Started` event, and ends with a `TouchPhase::Ended` or `TouchPhase::Cancelled`
+    /// one.
+    ///
+    /// Only supported on Wayland, and on X11 when the server supports XInput 2.4.
+    PinchGesture { device_id: DeviceId, delta: f64, phase: TouchPhase },
+
+    /// A rotation gesture on a touchpad, made by the fingers of a pinch gesture.
+    ///
+    /// `delta` is the angle the fingers turned since the last event, in degrees. Positive values
+    /// are counterclockwise.
+    ///
+    /// Only supported on Wayland, and on X11 when the server supports XInput 2.4.
+    RotationGesture { device_id: DeviceId, delta: f32, phase: TouchPhase },
+
+    /// A swipe gesture on a touchpad, made by `fingers` fingers moving together.
+    ///
+    /// `delta` is how far the fingers moved since the last event.
+    ///
+    /// Only supported on Wayland, and on X11 when the server supports XInput 2.4.
+    SwipeGesture { device_id: DeviceId, fingers: u32, delta: LogicalPosition, phase: TouchPhase },
+
     /// Motion on some analog axis. May report data redundant to other, more specific events.
     AxisMotion { device_id: DeviceId, axis: AxisId, value: f64 },
 
@@ -226,9 +300,26 @@ pub enum MouseButton {
     Left,
     Right,
     Middle,
+    /// The thumb button for going back, usually the first extra button of the mouse.
+    Back,
+    /// The thumb button for going forward, usually the second extra button of the mouse.
+    Forward,
+    /// Any other button. On X11 and Wayland, these are numbered the way X11 numbers them, so the
+    /// first one after `Forward` is `Other(10)`.
     Other(u8),
 }
 
+/// What happens to the data in a drag and drop operation.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum DndAction {
+    /// The data is copied to the drop target.
+    Copy,
+    /// The data is moved to the drop target, so the source should delete its copy.
+    Move,
+    /// The drop target makes a link to the data. Not supported on Wayland.
+    Link,
+}
+
 /// Describes a difference in the mouse scroll wheel state.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MouseScrollDelta {
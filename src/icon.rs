@@ -1,5 +1,6 @@
 use std::{fmt, mem};
 use std::error::Error;
+use std::time::Duration;
 #[cfg(feature = "icon_loading")]
 use std::io::{BufRead, Seek};
 #[cfg(feature = "icon_loading")]
@@ -35,6 +36,15 @@ pub enum BadIcon {
         width_x_height: usize,
         pixel_count: usize,
     },
+    /// Produced when the hotspot of a `CustomCursor` lies outside of its image.
+    HotspotOutOfBounds {
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    },
+    /// Produced when `CustomCursor::from_frames` is given no frames.
+    NoFrames,
 }
 
 impl fmt::Display for BadIcon {
@@ -53,6 +63,16 @@ impl fmt::Display for BadIcon {
                 "The specified dimensions ({:?}x{:?}) don't match the number of pixels supplied by the `rgba` argument ({:?}). For those dimensions, the expected pixel count is {:?}.",
                 width, height, pixel_count, width_x_height,
             ),
+            &BadIcon::HotspotOutOfBounds {
+                width,
+                height,
+                hotspot_x,
+                hotspot_y,
+            } => format!(
+                "The hotspot ({:?}, {:?}) lies outside of the {:?}x{:?} cursor image.",
+                hotspot_x, hotspot_y, width, height,
+            ),
+            &BadIcon::NoFrames => "An animated cursor needs at least one frame.".to_owned(),
         };
         write!(formatter, "{}", msg)
     }
@@ -142,6 +162,78 @@ impl Icon {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CursorFrame {
+    pub(crate) icon: Icon,
+    pub(crate) hotspot_x: u32,
+    pub(crate) hotspot_y: u32,
+    // How long the frame is shown for, if the cursor is animated.
+    pub(crate) delay: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A cursor image for `Window::set_custom_cursor`, which may be animated.
+///
+/// The image is shown at the same logical size on every monitor, and scaled up on those with a
+/// higher DPI factor.
+pub struct CustomCursor {
+    pub(crate) frames: Vec<CursorFrame>,
+}
+
+impl CustomCursor {
+    /// Creates a `CustomCursor` from 32bpp RGBA data.
+    ///
+    /// `hotspot_x` and `hotspot_y` are the pixel of the image that the cursor points at. `rgba`
+    /// is checked the same way as by `Icon::from_rgba`, and the hotspot must lie within the image.
+    pub fn from_rgba(
+        rgba: Vec<u8>,
+        width: u32,
+        height: u32,
+        hotspot_x: u32,
+        hotspot_y: u32,
+    ) -> Result<Self, BadIcon> {
+        Icon::from_rgba(rgba, width, height)
+            .and_then(|icon| CustomCursor::from_icon(icon, hotspot_x, hotspot_y))
+    }
+
+    /// Creates a `CustomCursor` from an `Icon`, such as one loaded with the `icon_loading`
+    /// feature.
+    pub fn from_icon(icon: Icon, hotspot_x: u32, hotspot_y: u32) -> Result<Self, BadIcon> {
+        if hotspot_x >= icon.width || hotspot_y >= icon.height {
+            Err(BadIcon::HotspotOutOfBounds {
+                width: icon.width,
+                height: icon.height,
+                hotspot_x,
+                hotspot_y,
+            })
+        } else {
+            Ok(CustomCursor {
+                frames: vec![CursorFrame { icon, hotspot_x, hotspot_y, delay: Duration::from_secs(0) }],
+            })
+        }
+    }
+
+    /// Creates an animated cursor, which shows each of `frames` for its duration, in order and
+    /// on a loop.
+    ///
+    /// The frames are usually created with `from_rgba` or `from_icon`. Each of them can have its
+    /// own size and hotspot. A frame that is animated itself has all of its frames shown for the
+    /// given duration.
+    pub fn from_frames(frames: Vec<(CustomCursor, Duration)>) -> Result<Self, BadIcon> {
+        let frames: Vec<_> = frames
+            .into_iter()
+            .flat_map(|(cursor, delay)| {
+                cursor.frames.into_iter().map(move |frame| CursorFrame { delay, ..frame })
+            })
+            .collect();
+        if frames.is_empty() {
+            Err(BadIcon::NoFrames)
+        } else {
+            Ok(CustomCursor { frames })
+        }
+    }
+}
+
 #[cfg(feature = "icon_loading")]
 /// Requires the `icon_loading` feature.
 impl From<image::DynamicImage> for Icon {
@@ -224,6 +224,18 @@ impl EventsLoop {
         MonitorId { inner: self.events_loop.get_primary_monitor() }
     }
 
+    /// Returns the current state of the pointer.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only implemented on X11 and Wayland; returns `None` elsewhere. On Wayland the state is kept
+    /// from the last pointer events, so `root_position` is always `None`, the window is only
+    /// known while the cursor is over it, and `modifiers` are not reported.
+    #[inline]
+    pub fn get_pointer_state(&self) -> Option<PointerState> {
+        self.events_loop.get_pointer_state()
+    }
+
     /// Fetches all the events that are pending, calls the callback function for each of them,
     /// and returns.
     #[inline]
@@ -392,6 +404,37 @@ impl Default for MouseCursor {
     }
 }
 
+/// Describes how the cursor is grabbed by a window.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CursorGrabMode {
+    /// The cursor moves freely.
+    None,
+    /// The cursor can move, but can't leave the window.
+    Confined,
+    /// The cursor stays where it is. Its motion is still reported by `DeviceEvent::MouseMotion`.
+    Locked,
+}
+
+impl Default for CursorGrabMode {
+    fn default() -> Self {
+        CursorGrabMode::None
+    }
+}
+
+/// The state of the pointer, as returned by `EventsLoop::get_pointer_state`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerState {
+    /// The position of the cursor on the screen, in physical pixels, if it's known.
+    pub root_position: Option<PhysicalPosition>,
+    /// The window the cursor is over, if it's one of ours, with the position of the cursor
+    /// relative to its top-left corner.
+    pub window: Option<(WindowId, LogicalPosition)>,
+    /// The mouse buttons that are held down.
+    pub buttons: Vec<MouseButton>,
+    /// The modifiers that are active.
+    pub modifiers: ModifiersState,
+}
+
 /// Attributes to use when creating a window.
 #[derive(Debug, Clone)]
 pub struct WindowAttributes {
@@ -5,7 +5,9 @@ use std::ptr;
 use std::sync::Arc;
 
 use {
+    DndAction,
     EventsLoop,
+    LogicalPosition,
     LogicalSize,
     MonitorId,
     Window,
@@ -22,7 +24,7 @@ use platform::x11::ffi::XVisualInfo;
 #[doc(hidden)]
 pub use platform::x11;
 
-pub use platform::XNotSupported;
+pub use platform::{ClipboardData, XNotSupported};
 pub use platform::x11::util::WindowType as XWindowType;
 
 /// Additional methods on `EventsLoop` that are specific to Linux.
@@ -41,6 +43,14 @@ pub trait EventsLoopExt {
     /// True if the `EventsLoop` uses X11.
     fn is_x11(&self) -> bool;
 
+    /// Sets the cursor theme and size used by the windows of this `EventsLoop`, replacing the ones from
+    /// the environment.
+    ///
+    /// By default, or when `None` is given, they come from `XCURSOR_THEME` and `XCURSOR_SIZE`, or on X11
+    /// from the `Xcursor.theme` and `Xcursor.size` resources. The size is scaled by each window's DPI
+    /// factor.
+    fn set_cursor_theme(&self, theme: Option<&str>, size: Option<u32>);
+
     #[doc(hidden)]
     fn get_xlib_xconnection(&self) -> Option<Arc<XConnection>>;
 }
@@ -77,6 +87,11 @@ impl EventsLoopExt for EventsLoop {
         !self.events_loop.is_wayland()
     }
 
+    #[inline]
+    fn set_cursor_theme(&self, theme: Option<&str>, size: Option<u32>) {
+        self.events_loop.set_cursor_theme(theme, size);
+    }
+
     #[inline]
     #[doc(hidden)]
     fn get_xlib_xconnection(&self) -> Option<Arc<XConnection>> {
@@ -127,6 +142,87 @@ pub trait WindowExt {
     /// The pointer will become invalid when the glutin `Window` is destroyed.
     fn get_wayland_display(&self) -> Option<*mut raw::c_void>;
 
+    /// Puts `text` on the clipboard.
+    ///
+    /// The text is served from the `EventsLoop`, so it stays available to other applications for
+    /// as long as it keeps running, even once this window is closed.
+    ///
+    /// On X11, this fails if another application took the clipboard at the same time.
+    fn set_clipboard_text(&self, text: &str) -> Result<(), String>;
+
+    /// Returns the text on the clipboard, if there is any.
+    ///
+    /// This blocks until the application owning the clipboard has sent its contents, and gives up
+    /// if the application stops responding for a second.
+    fn get_clipboard_text(&self) -> Option<String>;
+
+    /// Puts data on the clipboard in several formats at once, as pairs of MIME types and data.
+    ///
+    /// Other applications pick the format they understand best, so you can offer an image as
+    /// both `image/png` and `image/bmp`, or rich text as `text/html` alongside `text/plain`.
+    /// `ClipboardData::Lazy` lets you only produce the formats that are actually requested.
+    ///
+    /// On X11, MIME types are used as the names of the selection targets. This fails the same way
+    /// as `set_clipboard_text`.
+    fn set_clipboard_contents(&self, contents: Vec<(String, ClipboardData)>) -> Result<(), String>;
+
+    /// Returns the MIME types the contents of the clipboard are available as.
+    ///
+    /// On X11, these are the names of the targets the clipboard owner reports, which can include
+    /// ones that aren't MIME types, such as `UTF8_STRING`.
+    fn get_clipboard_mime_types(&self) -> Vec<String>;
+
+    /// Returns the contents of the clipboard as `mime_type`, or `None` if they aren't available
+    /// as that type.
+    ///
+    /// This blocks the same way as `get_clipboard_text`.
+    fn get_clipboard_data(&self, mime_type: &str) -> Option<Vec<u8>>;
+
+    /// Puts `text` on the primary selection, which is what a middle click pastes.
+    ///
+    /// Applications usually set it to the text the user last selected. On Wayland, this requires
+    /// the compositor to support the `zwp_primary_selection_device_manager_v1` protocol. This fails
+    /// the same way as `set_clipboard_text`.
+    fn set_primary_selection_text(&self, text: &str) -> Result<(), String>;
+
+    /// Returns the text on the primary selection, if there is any.
+    ///
+    /// This blocks the same way as `get_clipboard_text`.
+    fn get_primary_selection_text(&self) -> Option<String>;
+
+    /// Starts dragging data out of the window, offering it in several formats like
+    /// `set_clipboard_contents`.
+    ///
+    /// Call this while a mouse button is held down, usually in response to the cursor moving
+    /// with the button pressed. `actions` are what the drop target is allowed to do with the data,
+    /// in order of preference, and `WindowEvent::DragEnded` reports which one it did once the
+    /// drag is over.
+    ///
+    /// On Wayland, this fails if no mouse button is held down over the window. On X11, it fails if
+    /// the cursor can't be grabbed for the drag.
+    fn start_drag(&self, contents: Vec<(String, ClipboardData)>, actions: &[DndAction]) -> Result<(), String>;
+
+    /// Sets the MIME types of data, other than files, that can be dropped into the window, in
+    /// order of preference.
+    ///
+    /// A drag offering one of these types is accepted, and dropping it sends
+    /// `WindowEvent::DroppedData` with the data as the first of them that the drag offers. Files
+    /// are still reported through `DroppedFile` regardless. No types are accepted by default.
+    fn set_accepted_drop_types(&self, mime_types: &[&str]);
+
+    /// Answers a drag hovering over the window, usually in response to `WindowEvent::DragMoved`.
+    ///
+    /// `action` is what the window would do with the data if it were dropped there, or `None` to
+    /// reject the drop. `area` is the part of the window, if any, where the same answer holds,
+    /// which spares the drag source from asking again while the cursor stays inside it.
+    ///
+    /// The answer holds until it's changed, and every drag entering the window starts out
+    /// accepted with `DndAction::Copy`. Drags that offer neither files nor one of the types set
+    /// with `set_accepted_drop_types` are always rejected.
+    ///
+    /// On Wayland, `DndAction::Link` is answered as `Copy` and `area` is ignored.
+    fn set_drop_response(&self, action: Option<DndAction>, area: Option<(LogicalPosition, LogicalSize)>);
+
     /// Check if the window is ready for drawing
     ///
     /// It is a remnant of a previous implementation detail for the
@@ -186,6 +282,56 @@ impl WindowExt for Window {
         }
     }
 
+    #[inline]
+    fn set_clipboard_text(&self, text: &str) -> Result<(), String> {
+        self.window.set_clipboard_text(text)
+    }
+
+    #[inline]
+    fn get_clipboard_text(&self) -> Option<String> {
+        self.window.get_clipboard_text()
+    }
+
+    #[inline]
+    fn set_clipboard_contents(&self, contents: Vec<(String, ClipboardData)>) -> Result<(), String> {
+        self.window.set_clipboard_contents(contents)
+    }
+
+    #[inline]
+    fn get_clipboard_mime_types(&self) -> Vec<String> {
+        self.window.get_clipboard_mime_types()
+    }
+
+    #[inline]
+    fn get_clipboard_data(&self, mime_type: &str) -> Option<Vec<u8>> {
+        self.window.get_clipboard_data(mime_type)
+    }
+
+    #[inline]
+    fn set_primary_selection_text(&self, text: &str) -> Result<(), String> {
+        self.window.set_primary_selection_text(text)
+    }
+
+    #[inline]
+    fn get_primary_selection_text(&self) -> Option<String> {
+        self.window.get_primary_selection_text()
+    }
+
+    #[inline]
+    fn start_drag(&self, contents: Vec<(String, ClipboardData)>, actions: &[DndAction]) -> Result<(), String> {
+        self.window.start_drag(contents, actions)
+    }
+
+    #[inline]
+    fn set_accepted_drop_types(&self, mime_types: &[&str]) {
+        self.window.set_accepted_drop_types(mime_types)
+    }
+
+    #[inline]
+    fn set_drop_response(&self, action: Option<DndAction>, area: Option<(LogicalPosition, LogicalSize)>) {
+        self.window.set_drop_response(action, area)
+    }
+
     #[inline]
     fn get_wayland_surface(&self) -> Option<*mut raw::c_void> {
         match self.window {
@@ -56,6 +56,12 @@ impl EventsLoop {
         MonitorId
     }
 
+    #[inline]
+    pub fn get_pointer_state(&self) -> Option<::PointerState> {
+        // N/A
+        None
+    }
+
     pub fn poll_events<F>(&mut self, mut callback: F)
         where F: FnMut(::Event)
     {
@@ -336,7 +342,17 @@ impl Window {
     }
 
     #[inline]
-    pub fn grab_cursor(&self, _grab: bool) -> Result<(), String> {
+    pub fn set_custom_cursor(&self, _cursor: ::CustomCursor) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_cursor_grab(&self, _mode: ::CursorGrabMode) -> Result<(), String> {
+        Err("Cursor grabbing is not possible on Android.".to_owned())
+    }
+
+    #[inline]
+    pub fn confine_cursor_to(&self, _area: Option<(LogicalPosition, LogicalSize)>) -> Result<(), String> {
         Err("Cursor grabbing is not possible on Android.".to_owned())
     }
 
@@ -345,6 +361,12 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn get_cursor_position(&self) -> Option<LogicalPosition> {
+        // N/A
+        None
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), String> {
         Err("Setting cursor position is not possible on Android.".to_owned())
@@ -382,6 +404,16 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn set_ime_allowed(&self, _allowed: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_ime_cursor_area(&self, _position: LogicalPosition, _size: LogicalSize) {
+        // N/A
+    }
+
     #[inline]
     pub fn get_current_monitor(&self) -> RootMonitorId {
         RootMonitorId { inner: MonitorId }
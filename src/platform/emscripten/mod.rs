@@ -118,6 +118,12 @@ impl EventsLoop {
         MonitorId
     }
 
+    #[inline]
+    pub fn get_pointer_state(&self) -> Option<::PointerState> {
+        // N/A
+        None
+    }
+
     pub fn poll_events<F>(&self, mut callback: F)
         where F: FnMut(::Event)
     {
@@ -222,6 +228,8 @@ extern "C" fn mouse_callback(
                     0 => ::MouseButton::Left,
                     1 => ::MouseButton::Middle,
                     2 => ::MouseButton::Right,
+                    3 => ::MouseButton::Back,
+                    4 => ::MouseButton::Forward,
                     other => ::MouseButton::Other(other as u8),
                 };
                 let state = match mouse_input {
@@ -515,7 +523,14 @@ impl Window {
     }
 
     #[inline]
-    pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
+    pub fn set_custom_cursor(&self, _cursor: ::CustomCursor) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_cursor_grab(&self, mode: ::CursorGrabMode) -> Result<(), String> {
+        // The pointer lock API is all there is, so both modes lock the pointer.
+        let grab = mode != ::CursorGrabMode::None;
         let mut grabbed_lock = self.window.cursor_grabbed.lock().unwrap();
         if grab == *grabbed_lock { return Ok(()); }
         unsafe {
@@ -541,6 +556,12 @@ impl Window {
         Ok(())
     }
 
+    #[inline]
+    pub fn confine_cursor_to(&self, area: Option<(LogicalPosition, LogicalSize)>) -> Result<(), String> {
+        // N/A: the pointer lock API can't confine the pointer to an area.
+        self.set_cursor_grab(if area.is_some() { ::CursorGrabMode::Confined } else { ::CursorGrabMode::None })
+    }
+
     #[inline]
     pub fn hide_cursor(&self, hide: bool) {
         let mut hidden_lock = self.window.cursor_hidden.lock().unwrap();
@@ -558,6 +579,12 @@ impl Window {
         get_hidpi_factor()
     }
 
+    #[inline]
+    pub fn get_cursor_position(&self) -> Option<LogicalPosition> {
+        // N/A
+        None
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, _position: LogicalPosition) -> Result<(), String> {
         Err("Setting cursor position is not possible on Emscripten.".to_owned())
@@ -593,6 +620,16 @@ impl Window {
         // N/A
     }
 
+    #[inline]
+    pub fn set_ime_allowed(&self, _allowed: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_ime_cursor_area(&self, _position: LogicalPosition, _size: LogicalSize) {
+        // N/A
+    }
+
     #[inline]
     pub fn get_current_monitor(&self) -> RootMonitorId {
         RootMonitorId { inner: MonitorId }
@@ -622,7 +659,7 @@ impl Drop for Window {
         unsafe {
             // Return back to normal cursor state
             self.hide_cursor(false);
-            self.grab_cursor(false);
+            let _ = self.set_cursor_grab(::CursorGrabMode::None);
 
             // Exit fullscreen if on
             if self.window.is_fullscreen {
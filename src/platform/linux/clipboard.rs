@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+use std::fmt;
+
+// The mime types text is offered and accepted as, in order of preference.
+pub const TEXT_MIME_TYPES: &[&str] = &["text/plain;charset=utf-8", "UTF8_STRING", "text/plain"];
+
+/// The data put on the clipboard for one MIME type.
+pub enum ClipboardData {
+    /// The data itself.
+    Bytes(Vec<u8>),
+    /// A function returning the data, called on the thread running the `EventsLoop` each time
+    /// another application asks for it.
+    ///
+    /// This avoids encoding formats that may never be pasted.
+    Lazy(Box<Fn() -> Vec<u8> + Send + Sync>),
+}
+
+impl ClipboardData {
+    /// Returns the data, calling the function if it's `Lazy`.
+    pub fn get(&self) -> Cow<[u8]> {
+        match *self {
+            ClipboardData::Bytes(ref bytes) => Cow::Borrowed(bytes),
+            ClipboardData::Lazy(ref provider) => Cow::Owned(provider()),
+        }
+    }
+}
+
+impl fmt::Debug for ClipboardData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ClipboardData::Bytes(ref bytes) => f.debug_tuple("Bytes").field(bytes).finish(),
+            ClipboardData::Lazy(_) => f.debug_tuple("Lazy").field(&"..").finish(),
+        }
+    }
+}
+
+// What we offer on a selection, as pairs of mime types and data.
+pub type ClipboardContents = Vec<(String, ClipboardData)>;
+
+pub fn text_contents(text: &str) -> ClipboardContents {
+    TEXT_MIME_TYPES
+        .iter()
+        .map(|&mime_type| (mime_type.to_owned(), ClipboardData::Bytes(text.as_bytes().to_vec())))
+        .collect()
+}
+
+// Returns the data for the first of `mime_types` in `contents`.
+pub fn find_data(contents: &[(String, ClipboardData)], mime_types: &[&str]) -> Option<Vec<u8>> {
+    mime_types.iter().filter_map(|&mime_type| {
+        contents.iter().find(|&&(ref m, _)| m == mime_type)
+    }).next().map(|&(_, ref data)| data.get().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+
+    use super::{find_data, text_contents, ClipboardData};
+
+    #[test]
+    fn find_data_prefers_earlier_mime_types() {
+        let contents = vec![
+            ("text/plain".to_owned(), ClipboardData::Bytes(b"plain".to_vec())),
+            ("text/html".to_owned(), ClipboardData::Bytes(b"<b>html</b>".to_vec())),
+        ];
+        assert_eq!(find_data(&contents, &["text/html", "text/plain"]), Some(b"<b>html</b>".to_vec()));
+        assert_eq!(find_data(&contents, &["image/png", "text/plain"]), Some(b"plain".to_vec()));
+        assert_eq!(find_data(&contents, &["image/png"]), None);
+    }
+
+    #[test]
+    fn lazy_data_is_produced_on_each_request() {
+        static CALLS: AtomicUsize = ATOMIC_USIZE_INIT;
+        let contents = vec![(
+            "image/png".to_owned(),
+            ClipboardData::Lazy(Box::new(|| {
+                CALLS.fetch_add(1, Ordering::SeqCst);
+                vec![0x89, b'P', b'N', b'G']
+            })),
+        )];
+        assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+        assert_eq!(find_data(&contents, &["image/png"]), Some(vec![0x89, b'P', b'N', b'G']));
+        assert_eq!(find_data(&contents, &["image/png"]), Some(vec![0x89, b'P', b'N', b'G']));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn text_is_offered_as_every_text_mime_type() {
+        let contents = text_contents("hello");
+        assert_eq!(find_data(&contents, &["UTF8_STRING"]), Some(b"hello".to_vec()));
+        assert_eq!(find_data(&contents, &["text/plain;charset=utf-8"]), Some(b"hello".to_vec()));
+        assert_eq!(find_data(&contents, &["text/plain"]), Some(b"hello".to_vec()));
+    }
+}
@@ -0,0 +1,147 @@
+use {CustomCursor, MouseCursor};
+use icon::CursorFrame;
+
+// The cursor last set with `set_cursor` or `set_custom_cursor`, shown unless it's hidden.
+#[derive(Debug, Clone)]
+pub enum Cursor {
+    Named(MouseCursor),
+    Custom(CustomCursor),
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Cursor::Named(MouseCursor::default())
+    }
+}
+
+// The cursor theme set with `EventsLoopExt::set_cursor_theme`, replacing the one from the
+// environment where it's set. The size is in logical pixels.
+#[derive(Debug, Clone, Default)]
+pub struct ThemeOverride {
+    pub name: Option<String>,
+    pub size: Option<u32>,
+}
+
+// The names of the cursor in XCursor themes, to be tried in order.
+pub fn theme_names(cursor: MouseCursor) -> &'static [&'static str] {
+    // Try multiple names in some cases where the name
+    // differs on the desktop environments or themes.
+    //
+    // Try the better looking (or more suiting) names first.
+    match cursor {
+        MouseCursor::Alias => &["link"],
+        MouseCursor::Arrow => &["arrow"],
+        MouseCursor::Cell => &["plus"],
+        MouseCursor::Copy => &["copy"],
+        MouseCursor::Crosshair => &["crosshair"],
+        MouseCursor::Default => &["left_ptr"],
+        MouseCursor::Hand => &["hand2", "hand1"],
+        MouseCursor::Help => &["question_arrow"],
+        MouseCursor::Move => &["move"],
+        MouseCursor::Grab => &["openhand", "grab"],
+        MouseCursor::Grabbing => &["closedhand", "grabbing"],
+        MouseCursor::Progress => &["left_ptr_watch"],
+        MouseCursor::AllScroll => &["all-scroll"],
+        MouseCursor::ContextMenu => &["context-menu"],
+
+        MouseCursor::NoDrop => &["no-drop", "circle"],
+        MouseCursor::NotAllowed => &["crossed_circle"],
+
+        // Resize cursors
+        MouseCursor::EResize => &["right_side"],
+        MouseCursor::NResize => &["top_side"],
+        MouseCursor::NeResize => &["top_right_corner"],
+        MouseCursor::NwResize => &["top_left_corner"],
+        MouseCursor::SResize => &["bottom_side"],
+        MouseCursor::SeResize => &["bottom_right_corner"],
+        MouseCursor::SwResize => &["bottom_left_corner"],
+        MouseCursor::WResize => &["left_side"],
+        MouseCursor::EwResize => &["h_double_arrow"],
+        MouseCursor::NsResize => &["v_double_arrow"],
+        MouseCursor::NwseResize => &["bd_double_arrow", "size_bdiag"],
+        MouseCursor::NeswResize => &["fd_double_arrow", "size_fdiag"],
+        MouseCursor::ColResize => &["split_h", "h_double_arrow"],
+        MouseCursor::RowResize => &["split_v", "v_double_arrow"],
+
+        MouseCursor::Text => &["text", "xterm"],
+        MouseCursor::VerticalText => &["vertical-text"],
+
+        MouseCursor::Wait => &["watch"],
+
+        MouseCursor::ZoomIn => &["zoom-in"],
+        MouseCursor::ZoomOut => &["zoom-out"],
+    }
+}
+
+// A frame of a custom cursor scaled for a DPI factor, with its pixels as premultiplied ARGB,
+// which is what both Xcursor and `wl_shm` expect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorImage {
+    pub width: u32,
+    pub height: u32,
+    pub hotspot_x: u32,
+    pub hotspot_y: u32,
+    pub pixels: Vec<u32>,
+}
+
+impl CursorImage {
+    pub fn new(frame: &CursorFrame, dpi_factor: f64) -> CursorImage {
+        let icon = &frame.icon;
+        let scale = |length: u32| ((length as f64 * dpi_factor).round() as u32).max(1);
+        let (width, height) = (scale(icon.width), scale(icon.height));
+
+        // Nearest neighbor keeps the edges of crosshairs and the like sharp.
+        let mut pixels = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            let src_y = ((y as f64 / dpi_factor) as u32).min(icon.height - 1);
+            for x in 0..width {
+                let src_x = ((x as f64 / dpi_factor) as u32).min(icon.width - 1);
+                let i = ((src_y * icon.width + src_x) * 4) as usize;
+                let (r, g, b, a) = (
+                    icon.rgba[i] as u32,
+                    icon.rgba[i + 1] as u32,
+                    icon.rgba[i + 2] as u32,
+                    icon.rgba[i + 3] as u32,
+                );
+                let premultiply = |channel: u32| channel * a / 255;
+                pixels.push((a << 24) | (premultiply(r) << 16) | (premultiply(g) << 8) | premultiply(b));
+            }
+        }
+
+        CursorImage {
+            width,
+            height,
+            hotspot_x: ((frame.hotspot_x as f64 * dpi_factor) as u32).min(width - 1),
+            hotspot_y: ((frame.hotspot_y as f64 * dpi_factor) as u32).min(height - 1),
+            pixels,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use CustomCursor;
+    use super::CursorImage;
+
+    #[test]
+    fn pixels_are_premultiplied_argb() {
+        let cursor = CustomCursor::from_rgba(vec![255, 128, 0, 255, 255, 255, 255, 0], 2, 1, 0, 0)
+            .unwrap();
+        let image = CursorImage::new(&cursor.frames[0], 1.0);
+        assert_eq!(image.pixels, vec![0xffff8000, 0x00000000]);
+    }
+
+    #[test]
+    fn cursor_is_scaled_with_its_hotspot() {
+        let rgba = vec![
+            255, 0, 0, 255, 0, 255, 0, 255,
+            0, 0, 255, 255, 0, 0, 0, 255,
+        ];
+        let cursor = CustomCursor::from_rgba(rgba, 2, 2, 1, 1).unwrap();
+        let image = CursorImage::new(&cursor.frames[0], 2.0);
+        assert_eq!((image.width, image.height), (4, 4));
+        assert_eq!((image.hotspot_x, image.hotspot_y), (2, 2));
+        assert_eq!(&image.pixels[..4], &[0xffff0000, 0xffff0000, 0xff00ff00, 0xff00ff00]);
+        assert_eq!(&image.pixels[12..], &[0xff0000ff, 0xff0000ff, 0xff000000, 0xff000000]);
+    }
+}
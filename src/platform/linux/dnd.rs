@@ -0,0 +1,74 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::Utf8Error;
+
+use percent_encoding::percent_decode;
+
+use DndAction;
+use dpi::{LogicalPosition, LogicalSize};
+
+#[derive(Debug)]
+pub enum DndDataParseError {
+    EmptyData,
+    InvalidUtf8(Utf8Error),
+    HostnameSpecified(String),
+    UnexpectedProtocol(String),
+    UnresolvablePath(io::Error),
+}
+
+impl From<Utf8Error> for DndDataParseError {
+    fn from(e: Utf8Error) -> Self {
+        DndDataParseError::InvalidUtf8(e)
+    }
+}
+
+impl From<io::Error> for DndDataParseError {
+    fn from(e: io::Error) -> Self {
+        DndDataParseError::UnresolvablePath(e)
+    }
+}
+
+// How a window answers drags over it, as set with `WindowExt::set_drop_response`.
+#[derive(Debug, Clone, Copy)]
+pub struct DropResponse {
+    // `None` rejects the drop.
+    pub action: Option<DndAction>,
+    // The part of the window where the answer holds, if it's the same all over it.
+    pub area: Option<(LogicalPosition, LogicalSize)>,
+}
+
+impl Default for DropResponse {
+    fn default() -> Self {
+        DropResponse { action: Some(DndAction::Copy), area: None }
+    }
+}
+
+// Parses a `text/uri-list`, which must only contain local files.
+pub fn parse_uri_list(data: &[u8]) -> Result<Vec<PathBuf>, DndDataParseError> {
+    if !data.is_empty() {
+        let mut path_list = Vec::new();
+        let decoded = percent_decode(data).decode_utf8()?.into_owned();
+        for uri in decoded.split("\r\n").filter(|u| !u.is_empty()) {
+            // The format is specified as protocol://host/path
+            // However, it's typically simply protocol:///path
+            let path_str = if uri.starts_with("file://") {
+                let path_str = uri.replace("file://", "");
+                if !path_str.starts_with('/') {
+                    // A hostname is specified
+                    // Supporting this case is beyond the scope of my mental health
+                    return Err(DndDataParseError::HostnameSpecified(path_str));
+                }
+                path_str
+            } else {
+                // Only the file protocol is supported
+                return Err(DndDataParseError::UnexpectedProtocol(uri.to_owned()));
+            };
+
+            let path = Path::new(&path_str).canonicalize()?;
+            path_list.push(path);
+        }
+        Ok(path_list)
+    } else {
+        Err(DndDataParseError::EmptyData)
+    }
+}
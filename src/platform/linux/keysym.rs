@@ -0,0 +1,277 @@
+#![cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
+
+//! Keysym to `VirtualKeyCode` translation shared by the X11 and Wayland backends.
+//!
+//! X11 keysyms and xkbcommon keysyms share the same numeric values, so a single table serves
+//! both backends.
+
+use x11_dl::keysym::*;
+
+use VirtualKeyCode;
+
+pub fn keysym_to_vkey(keysym: u32) -> Option<VirtualKeyCode> {
+    Some(match keysym {
+        // numbers
+        XK_1 => VirtualKeyCode::Key1,
+        XK_2 => VirtualKeyCode::Key2,
+        XK_3 => VirtualKeyCode::Key3,
+        XK_4 => VirtualKeyCode::Key4,
+        XK_5 => VirtualKeyCode::Key5,
+        XK_6 => VirtualKeyCode::Key6,
+        XK_7 => VirtualKeyCode::Key7,
+        XK_8 => VirtualKeyCode::Key8,
+        XK_9 => VirtualKeyCode::Key9,
+        XK_0 => VirtualKeyCode::Key0,
+        // letters
+        XK_A | XK_a => VirtualKeyCode::A,
+        XK_B | XK_b => VirtualKeyCode::B,
+        XK_C | XK_c => VirtualKeyCode::C,
+        XK_D | XK_d => VirtualKeyCode::D,
+        XK_E | XK_e => VirtualKeyCode::E,
+        XK_F | XK_f => VirtualKeyCode::F,
+        XK_G | XK_g => VirtualKeyCode::G,
+        XK_H | XK_h => VirtualKeyCode::H,
+        XK_I | XK_i => VirtualKeyCode::I,
+        XK_J | XK_j => VirtualKeyCode::J,
+        XK_K | XK_k => VirtualKeyCode::K,
+        XK_L | XK_l => VirtualKeyCode::L,
+        XK_M | XK_m => VirtualKeyCode::M,
+        XK_N | XK_n => VirtualKeyCode::N,
+        XK_O | XK_o => VirtualKeyCode::O,
+        XK_P | XK_p => VirtualKeyCode::P,
+        XK_Q | XK_q => VirtualKeyCode::Q,
+        XK_R | XK_r => VirtualKeyCode::R,
+        XK_S | XK_s => VirtualKeyCode::S,
+        XK_T | XK_t => VirtualKeyCode::T,
+        XK_U | XK_u => VirtualKeyCode::U,
+        XK_V | XK_v => VirtualKeyCode::V,
+        XK_W | XK_w => VirtualKeyCode::W,
+        XK_X | XK_x => VirtualKeyCode::X,
+        XK_Y | XK_y => VirtualKeyCode::Y,
+        XK_Z | XK_z => VirtualKeyCode::Z,
+        // F--
+        XK_Escape => VirtualKeyCode::Escape,
+        XK_F1 => VirtualKeyCode::F1,
+        XK_F2 => VirtualKeyCode::F2,
+        XK_F3 => VirtualKeyCode::F3,
+        XK_F4 => VirtualKeyCode::F4,
+        XK_F5 => VirtualKeyCode::F5,
+        XK_F6 => VirtualKeyCode::F6,
+        XK_F7 => VirtualKeyCode::F7,
+        XK_F8 => VirtualKeyCode::F8,
+        XK_F9 => VirtualKeyCode::F9,
+        XK_F10 => VirtualKeyCode::F10,
+        XK_F11 => VirtualKeyCode::F11,
+        XK_F12 => VirtualKeyCode::F12,
+        XK_F13 => VirtualKeyCode::F13,
+        XK_F14 => VirtualKeyCode::F14,
+        XK_F15 => VirtualKeyCode::F15,
+        // flow control
+        XK_Print => VirtualKeyCode::Snapshot,
+        XK_Scroll_Lock => VirtualKeyCode::Scroll,
+        XK_Pause => VirtualKeyCode::Pause,
+        XK_Insert | XK_KP_Insert => VirtualKeyCode::Insert,
+        XK_Home | XK_KP_Home => VirtualKeyCode::Home,
+        XK_Delete | XK_KP_Delete => VirtualKeyCode::Delete,
+        XK_End | XK_KP_End => VirtualKeyCode::End,
+        XK_Page_Down | XK_KP_Page_Down => VirtualKeyCode::PageDown,
+        XK_Page_Up | XK_KP_Page_Up => VirtualKeyCode::PageUp,
+        // arrows
+        XK_Left | XK_KP_Left => VirtualKeyCode::Left,
+        XK_Up | XK_KP_Up => VirtualKeyCode::Up,
+        XK_Right | XK_KP_Right => VirtualKeyCode::Right,
+        XK_Down | XK_KP_Down => VirtualKeyCode::Down,
+        //
+        XK_BackSpace => VirtualKeyCode::Back,
+        XK_Return => VirtualKeyCode::Return,
+        XK_space => VirtualKeyCode::Space,
+        XK_Multi_key => VirtualKeyCode::Compose,
+        XK_asciicircum => VirtualKeyCode::Caret,
+        // keypad
+        XK_Num_Lock => VirtualKeyCode::Numlock,
+        XK_KP_0 => VirtualKeyCode::Numpad0,
+        XK_KP_1 => VirtualKeyCode::Numpad1,
+        XK_KP_2 => VirtualKeyCode::Numpad2,
+        XK_KP_3 => VirtualKeyCode::Numpad3,
+        XK_KP_4 => VirtualKeyCode::Numpad4,
+        XK_KP_5 => VirtualKeyCode::Numpad5,
+        XK_KP_6 => VirtualKeyCode::Numpad6,
+        XK_KP_7 => VirtualKeyCode::Numpad7,
+        XK_KP_8 => VirtualKeyCode::Numpad8,
+        XK_KP_9 => VirtualKeyCode::Numpad9,
+        XK_KP_Add | XK_plus => VirtualKeyCode::Add,
+        XK_KP_Subtract => VirtualKeyCode::Subtract,
+        XK_KP_Multiply | XK_asterisk => VirtualKeyCode::Multiply,
+        XK_KP_Divide => VirtualKeyCode::Divide,
+        XK_KP_Decimal => VirtualKeyCode::Decimal,
+        XK_KP_Separator => VirtualKeyCode::NumpadComma,
+        XK_KP_Enter => VirtualKeyCode::NumpadEnter,
+        XK_KP_Equal => VirtualKeyCode::NumpadEquals,
+        // modifiers
+        XK_Caps_Lock => VirtualKeyCode::Capital,
+        XK_Shift_L => VirtualKeyCode::LShift,
+        XK_Shift_R => VirtualKeyCode::RShift,
+        XK_Control_L => VirtualKeyCode::LControl,
+        XK_Control_R => VirtualKeyCode::RControl,
+        XK_Alt_L => VirtualKeyCode::LAlt,
+        XK_Alt_R => VirtualKeyCode::RAlt,
+        XK_Super_L => VirtualKeyCode::LWin,
+        XK_Super_R => VirtualKeyCode::RWin,
+        // punctuation
+        XK_Tab | XK_ISO_Left_Tab => VirtualKeyCode::Tab,
+        XK_apostrophe => VirtualKeyCode::Apostrophe,
+        XK_at => VirtualKeyCode::At,
+        XK_backslash => VirtualKeyCode::Backslash,
+        XK_colon => VirtualKeyCode::Colon,
+        XK_comma => VirtualKeyCode::Comma,
+        XK_equal => VirtualKeyCode::Equals,
+        XK_grave => VirtualKeyCode::Grave,
+        XK_bracketleft => VirtualKeyCode::LBracket,
+        XK_bracketright => VirtualKeyCode::RBracket,
+        XK_minus => VirtualKeyCode::Minus,
+        XK_period => VirtualKeyCode::Period,
+        XK_semicolon => VirtualKeyCode::Semicolon,
+        XK_slash => VirtualKeyCode::Slash,
+        XK_underscore => VirtualKeyCode::Underline,
+        XK_yen => VirtualKeyCode::Yen,
+        // system
+        XK_Menu => VirtualKeyCode::Apps,
+        XK_Sys_Req => VirtualKeyCode::Sysrq,
+        XK_Cancel => VirtualKeyCode::Stop,
+        XF86XK_PowerOff => VirtualKeyCode::Power,
+        XF86XK_Sleep => VirtualKeyCode::Sleep,
+        XF86XK_WakeUp => VirtualKeyCode::Wake,
+        // input methods
+        XK_Henkan_Mode => VirtualKeyCode::Convert,
+        XK_Muhenkan => VirtualKeyCode::NoConvert,
+        XK_Kanji => VirtualKeyCode::Kanji,
+        XK_Katakana | XK_Hiragana_Katakana | XK_Kana_Lock | XK_Kana_Shift => VirtualKeyCode::Kana,
+        // multimedia
+        XF86XK_AudioMute => VirtualKeyCode::Mute,
+        XF86XK_AudioLowerVolume => VirtualKeyCode::VolumeDown,
+        XF86XK_AudioRaiseVolume => VirtualKeyCode::VolumeUp,
+        XF86XK_AudioPlay | XF86XK_AudioPause => VirtualKeyCode::PlayPause,
+        XF86XK_AudioStop => VirtualKeyCode::MediaStop,
+        XF86XK_AudioNext => VirtualKeyCode::NextTrack,
+        XF86XK_AudioPrev => VirtualKeyCode::PrevTrack,
+        XF86XK_AudioMedia => VirtualKeyCode::MediaSelect,
+        // applications
+        XF86XK_Calculator => VirtualKeyCode::Calculator,
+        XF86XK_Mail => VirtualKeyCode::Mail,
+        XF86XK_MyComputer => VirtualKeyCode::MyComputer,
+        // web
+        XF86XK_Back => VirtualKeyCode::NavigateBackward,
+        XF86XK_Forward => VirtualKeyCode::NavigateForward,
+        XF86XK_Favorites => VirtualKeyCode::WebFavorites,
+        XF86XK_HomePage => VirtualKeyCode::WebHome,
+        XF86XK_Refresh | XF86XK_Reload => VirtualKeyCode::WebRefresh,
+        XF86XK_Search => VirtualKeyCode::WebSearch,
+        XF86XK_Stop => VirtualKeyCode::WebStop,
+        // editing
+        XF86XK_Copy => VirtualKeyCode::Copy,
+        XF86XK_Paste => VirtualKeyCode::Paste,
+        XF86XK_Cut => VirtualKeyCode::Cut,
+        // fallback
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use VirtualKeyCode;
+    use super::keysym_to_vkey;
+
+    // Every keysym the table could possibly contain: the Latin-1 and function key ranges, plus the
+    // XF86 vendor range.
+    fn all_keysyms() -> ::std::iter::Chain<::std::ops::Range<u32>, ::std::ops::Range<u32>> {
+        (0..0x1_0000).chain(0x1008_FF00..0x1009_0000)
+    }
+
+    // These have no keysym of their own. Browser back and forward are reported as
+    // `NavigateBackward` and `NavigateForward`, as on Windows.
+    const UNMAPPED: &[VirtualKeyCode] = &[
+        VirtualKeyCode::AbntC1,
+        VirtualKeyCode::AbntC2,
+        VirtualKeyCode::Ax,
+        VirtualKeyCode::OEM102,
+        VirtualKeyCode::Unlabeled,
+        VirtualKeyCode::WebBack,
+        VirtualKeyCode::WebForward,
+    ];
+
+    const ALL_VKEYS: &[VirtualKeyCode] = &[
+        VirtualKeyCode::Key1, VirtualKeyCode::Key2, VirtualKeyCode::Key3, VirtualKeyCode::Key4,
+        VirtualKeyCode::Key5, VirtualKeyCode::Key6, VirtualKeyCode::Key7, VirtualKeyCode::Key8,
+        VirtualKeyCode::Key9, VirtualKeyCode::Key0,
+        VirtualKeyCode::A, VirtualKeyCode::B, VirtualKeyCode::C, VirtualKeyCode::D,
+        VirtualKeyCode::E, VirtualKeyCode::F, VirtualKeyCode::G, VirtualKeyCode::H,
+        VirtualKeyCode::I, VirtualKeyCode::J, VirtualKeyCode::K, VirtualKeyCode::L,
+        VirtualKeyCode::M, VirtualKeyCode::N, VirtualKeyCode::O, VirtualKeyCode::P,
+        VirtualKeyCode::Q, VirtualKeyCode::R, VirtualKeyCode::S, VirtualKeyCode::T,
+        VirtualKeyCode::U, VirtualKeyCode::V, VirtualKeyCode::W, VirtualKeyCode::X,
+        VirtualKeyCode::Y, VirtualKeyCode::Z,
+        VirtualKeyCode::Escape,
+        VirtualKeyCode::F1, VirtualKeyCode::F2, VirtualKeyCode::F3, VirtualKeyCode::F4,
+        VirtualKeyCode::F5, VirtualKeyCode::F6, VirtualKeyCode::F7, VirtualKeyCode::F8,
+        VirtualKeyCode::F9, VirtualKeyCode::F10, VirtualKeyCode::F11, VirtualKeyCode::F12,
+        VirtualKeyCode::F13, VirtualKeyCode::F14, VirtualKeyCode::F15,
+        VirtualKeyCode::Snapshot, VirtualKeyCode::Scroll, VirtualKeyCode::Pause,
+        VirtualKeyCode::Insert, VirtualKeyCode::Home, VirtualKeyCode::Delete, VirtualKeyCode::End,
+        VirtualKeyCode::PageDown, VirtualKeyCode::PageUp,
+        VirtualKeyCode::Left, VirtualKeyCode::Up, VirtualKeyCode::Right, VirtualKeyCode::Down,
+        VirtualKeyCode::Back, VirtualKeyCode::Return, VirtualKeyCode::Space,
+        VirtualKeyCode::Compose, VirtualKeyCode::Caret,
+        VirtualKeyCode::Numlock,
+        VirtualKeyCode::Numpad0, VirtualKeyCode::Numpad1, VirtualKeyCode::Numpad2,
+        VirtualKeyCode::Numpad3, VirtualKeyCode::Numpad4, VirtualKeyCode::Numpad5,
+        VirtualKeyCode::Numpad6, VirtualKeyCode::Numpad7, VirtualKeyCode::Numpad8,
+        VirtualKeyCode::Numpad9,
+        VirtualKeyCode::AbntC1, VirtualKeyCode::AbntC2, VirtualKeyCode::Add,
+        VirtualKeyCode::Apostrophe, VirtualKeyCode::Apps, VirtualKeyCode::At, VirtualKeyCode::Ax,
+        VirtualKeyCode::Backslash, VirtualKeyCode::Calculator, VirtualKeyCode::Capital,
+        VirtualKeyCode::Colon, VirtualKeyCode::Comma, VirtualKeyCode::Convert,
+        VirtualKeyCode::Decimal, VirtualKeyCode::Divide, VirtualKeyCode::Equals,
+        VirtualKeyCode::Grave, VirtualKeyCode::Kana, VirtualKeyCode::Kanji, VirtualKeyCode::LAlt,
+        VirtualKeyCode::LBracket, VirtualKeyCode::LControl, VirtualKeyCode::LShift,
+        VirtualKeyCode::LWin, VirtualKeyCode::Mail, VirtualKeyCode::MediaSelect,
+        VirtualKeyCode::MediaStop, VirtualKeyCode::Minus, VirtualKeyCode::Multiply,
+        VirtualKeyCode::Mute, VirtualKeyCode::MyComputer, VirtualKeyCode::NavigateForward,
+        VirtualKeyCode::NavigateBackward, VirtualKeyCode::NextTrack, VirtualKeyCode::NoConvert,
+        VirtualKeyCode::NumpadComma, VirtualKeyCode::NumpadEnter, VirtualKeyCode::NumpadEquals,
+        VirtualKeyCode::OEM102, VirtualKeyCode::Period, VirtualKeyCode::PlayPause,
+        VirtualKeyCode::Power, VirtualKeyCode::PrevTrack, VirtualKeyCode::RAlt,
+        VirtualKeyCode::RBracket, VirtualKeyCode::RControl, VirtualKeyCode::RShift,
+        VirtualKeyCode::RWin, VirtualKeyCode::Semicolon, VirtualKeyCode::Slash,
+        VirtualKeyCode::Sleep, VirtualKeyCode::Stop, VirtualKeyCode::Subtract,
+        VirtualKeyCode::Sysrq, VirtualKeyCode::Tab, VirtualKeyCode::Underline,
+        VirtualKeyCode::Unlabeled, VirtualKeyCode::VolumeDown, VirtualKeyCode::VolumeUp,
+        VirtualKeyCode::Wake, VirtualKeyCode::WebBack, VirtualKeyCode::WebFavorites,
+        VirtualKeyCode::WebForward, VirtualKeyCode::WebHome, VirtualKeyCode::WebRefresh,
+        VirtualKeyCode::WebSearch, VirtualKeyCode::WebStop, VirtualKeyCode::Yen,
+        VirtualKeyCode::Copy, VirtualKeyCode::Paste, VirtualKeyCode::Cut,
+    ];
+
+    #[test]
+    fn every_vkey_is_reachable() {
+        let mapped: HashSet<_> = all_keysyms().filter_map(keysym_to_vkey).collect();
+        for vkey in ALL_VKEYS {
+            if UNMAPPED.contains(vkey) {
+                assert!(!mapped.contains(vkey), "{:?} is listed as unmapped but has a keysym", vkey);
+            } else {
+                assert!(mapped.contains(vkey), "no keysym maps to {:?}", vkey);
+            }
+        }
+    }
+
+    #[test]
+    fn vkey_list_is_complete() {
+        // `VirtualKeyCode` is `repr(u32)` with implicit discriminants, so the list above covers
+        // every variant exactly when its length matches the last discriminant plus one.
+        assert_eq!(ALL_VKEYS.len(), VirtualKeyCode::Cut as usize + 1);
+        for (i, vkey) in ALL_VKEYS.iter().enumerate() {
+            assert_eq!(*vkey as usize, i, "{:?} is out of order", vkey);
+        }
+    }
+}
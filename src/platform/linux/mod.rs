@@ -11,10 +11,14 @@ use sctk::reexports::client::ConnectError;
 
 use {
     CreationError,
+    CursorGrabMode,
+    CustomCursor,
+    DndAction,
     EventsLoopClosed,
     Icon,
     MouseCursor,
     ControlFlow,
+    PointerState,
     WindowAttributes,
 };
 use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
@@ -22,8 +26,13 @@ use window::MonitorId as RootMonitorId;
 use self::x11::{XConnection, XError};
 use self::x11::ffi::XVisualInfo;
 pub use self::x11::XNotSupported;
+pub use self::clipboard::ClipboardData;
 
+mod clipboard;
+mod cursor;
 mod dlopen;
+mod dnd;
+mod keysym;
 pub mod wayland;
 pub mod x11;
 
@@ -248,10 +257,26 @@ impl Window {
     }
 
     #[inline]
-    pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
+    pub fn set_custom_cursor(&self, cursor: CustomCursor) {
         match self {
-            &Window::X(ref window) => window.grab_cursor(grab),
-            &Window::Wayland(ref window) => window.grab_cursor(grab),
+            &Window::X(ref w) => w.set_custom_cursor(cursor),
+            &Window::Wayland(ref w) => w.set_custom_cursor(cursor),
+        }
+    }
+
+    #[inline]
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), String> {
+        match self {
+            &Window::X(ref window) => window.set_cursor_grab(mode),
+            &Window::Wayland(ref window) => window.set_cursor_grab(mode),
+        }
+    }
+
+    #[inline]
+    pub fn confine_cursor_to(&self, area: Option<(LogicalPosition, LogicalSize)>) -> Result<(), String> {
+        match self {
+            &Window::X(ref window) => window.confine_cursor_to(area),
+            &Window::Wayland(ref window) => window.confine_cursor_to(area),
         }
     }
 
@@ -271,6 +296,14 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn get_cursor_position(&self) -> Option<LogicalPosition> {
+        match self {
+            &Window::X(ref w) => w.get_cursor_position(),
+            &Window::Wayland(ref w) => w.get_cursor_position(),
+        }
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, position: LogicalPosition) -> Result<(), String> {
         match self {
@@ -323,7 +356,103 @@ impl Window {
     pub fn set_ime_spot(&self, position: LogicalPosition) {
         match self {
             &Window::X(ref w) => w.set_ime_spot(position),
-            &Window::Wayland(_) => (),
+            &Window::Wayland(ref w) => w.set_ime_spot(position),
+        }
+    }
+
+    #[inline]
+    pub fn set_ime_cursor_area(&self, position: LogicalPosition, size: LogicalSize) {
+        match self {
+            &Window::X(ref w) => w.set_ime_cursor_area(position, size),
+            &Window::Wayland(ref w) => w.set_ime_cursor_area(position, size),
+        }
+    }
+
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        match self {
+            &Window::X(ref w) => w.set_ime_allowed(allowed),
+            &Window::Wayland(ref w) => w.set_ime_allowed(allowed),
+        }
+    }
+
+    #[inline]
+    pub fn set_clipboard_text(&self, text: &str) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.set_clipboard_text(text),
+            &Window::Wayland(ref w) => w.set_clipboard_text(text),
+        }
+    }
+
+    #[inline]
+    pub fn get_clipboard_text(&self) -> Option<String> {
+        match self {
+            &Window::X(ref w) => w.get_clipboard_text(),
+            &Window::Wayland(ref w) => w.get_clipboard_text(),
+        }
+    }
+
+    #[inline]
+    pub fn set_clipboard_contents(&self, contents: Vec<(String, ClipboardData)>) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.set_clipboard_contents(contents),
+            &Window::Wayland(ref w) => w.set_clipboard_contents(contents),
+        }
+    }
+
+    #[inline]
+    pub fn get_clipboard_mime_types(&self) -> Vec<String> {
+        match self {
+            &Window::X(ref w) => w.get_clipboard_mime_types(),
+            &Window::Wayland(ref w) => w.get_clipboard_mime_types(),
+        }
+    }
+
+    #[inline]
+    pub fn get_clipboard_data(&self, mime_type: &str) -> Option<Vec<u8>> {
+        match self {
+            &Window::X(ref w) => w.get_clipboard_data(mime_type),
+            &Window::Wayland(ref w) => w.get_clipboard_data(mime_type),
+        }
+    }
+
+    #[inline]
+    pub fn set_primary_selection_text(&self, text: &str) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.set_primary_selection_text(text),
+            &Window::Wayland(ref w) => w.set_primary_selection_text(text),
+        }
+    }
+
+    #[inline]
+    pub fn get_primary_selection_text(&self) -> Option<String> {
+        match self {
+            &Window::X(ref w) => w.get_primary_selection_text(),
+            &Window::Wayland(ref w) => w.get_primary_selection_text(),
+        }
+    }
+
+    #[inline]
+    pub fn start_drag(&self, contents: Vec<(String, ClipboardData)>, actions: &[DndAction]) -> Result<(), String> {
+        match self {
+            &Window::X(ref w) => w.start_drag(contents, actions),
+            &Window::Wayland(ref w) => w.start_drag(contents, actions),
+        }
+    }
+
+    #[inline]
+    pub fn set_accepted_drop_types(&self, mime_types: &[&str]) {
+        match self {
+            &Window::X(ref w) => w.set_accepted_drop_types(mime_types),
+            &Window::Wayland(ref w) => w.set_accepted_drop_types(mime_types),
+        }
+    }
+
+    #[inline]
+    pub fn set_drop_response(&self, action: Option<DndAction>, area: Option<(LogicalPosition, LogicalSize)>) {
+        match self {
+            &Window::X(ref w) => w.set_drop_response(action, area),
+            &Window::Wayland(ref w) => w.set_drop_response(action, area),
         }
     }
 
@@ -479,6 +608,22 @@ r#"Failed to initialize any backend!
         }
     }
 
+    #[inline]
+    pub fn get_pointer_state(&self) -> Option<PointerState> {
+        match *self {
+            EventsLoop::Wayland(ref evlp) => evlp.get_pointer_state(),
+            EventsLoop::X(ref evlp) => evlp.get_pointer_state(),
+        }
+    }
+
+    #[inline]
+    pub fn set_cursor_theme(&self, theme: Option<&str>, size: Option<u32>) {
+        match *self {
+            EventsLoop::Wayland(ref evlp) => evlp.set_cursor_theme(theme, size),
+            EventsLoop::X(ref evlp) => evlp.set_cursor_theme(theme, size),
+        }
+    }
+
     pub fn create_proxy(&self) -> EventsLoopProxy {
         match *self {
             EventsLoop::Wayland(ref evlp) => EventsLoopProxy::Wayland(evlp.create_proxy()),
@@ -0,0 +1,367 @@
+use std::{cmp, env, mem};
+use std::io::{Seek, SeekFrom, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use MouseCursor;
+
+use sctk::reexports::client::Proxy;
+use sctk::reexports::client::cursor::{is_available, load_theme, CursorTheme};
+use sctk::reexports::client::protocol::{wl_buffer, wl_compositor, wl_pointer, wl_shm, wl_surface};
+use sctk::reexports::client::protocol::wl_buffer::RequestsTrait as BufferRequests;
+use sctk::reexports::client::protocol::wl_compositor::RequestsTrait as CompositorRequests;
+use sctk::reexports::client::protocol::wl_pointer::RequestsTrait as PointerRequests;
+use sctk::reexports::client::protocol::wl_surface::RequestsTrait as SurfaceRequests;
+use sctk::utils::MemPool;
+
+use super::super::cursor::{theme_names, Cursor, CursorImage, ThemeOverride};
+use super::tasks::LoopTasks;
+
+// A frame of a cursor, in its own buffer.
+struct Frame {
+    buffer: Proxy<wl_buffer::WlBuffer>,
+    // Whether we drew the buffer, rather than the cursor theme.
+    owned: bool,
+    // The size and hotspot are in surface coordinates, which the buffer scale accounts for.
+    size: (i32, i32),
+    hotspot: (i32, i32),
+    delay: Duration,
+}
+
+// The cursor theme at each scale the windows are shown at, shared between them.
+pub struct CursorThemes {
+    shm: Proxy<wl_shm::WlShm>,
+    name: Option<String>,
+    size: u32,
+    themes: Vec<(i32, CursorTheme)>,
+}
+
+impl CursorThemes {
+    pub fn new(shm: &Proxy<wl_shm::WlShm>) -> CursorThemes {
+        let mut themes = CursorThemes {
+            shm: shm.clone(),
+            name: None,
+            size: 0,
+            themes: Vec::new(),
+        };
+        themes.set_theme(ThemeOverride::default());
+        themes
+    }
+
+    // The windows have to be redrawn to use the new theme, and the old themes have to outlive that,
+    // as their buffers are attached until then.
+    pub fn set_theme(&mut self, theme: ThemeOverride) -> Vec<(i32, CursorTheme)> {
+        // The same variables Xcursor reads on X11.
+        self.name = theme.name.or_else(|| env::var("XCURSOR_THEME").ok());
+        self.size = theme.size
+            .or_else(|| env::var("XCURSOR_SIZE").ok().and_then(|size| size.parse().ok()))
+            .unwrap_or(24);
+        mem::replace(&mut self.themes, Vec::new())
+    }
+
+    // Returns the frames of the cursor, or of the default one if the theme lacks it.
+    fn frames(&mut self, cursor: MouseCursor, scale: i32) -> Vec<Frame> {
+        if !is_available() {
+            return Vec::new();
+        }
+        if !self.themes.iter().any(|&(s, _)| s == scale) {
+            let theme = load_theme(
+                self.name.as_ref().map(String::as_str),
+                self.size * scale as u32,
+                &self.shm,
+            );
+            self.themes.push((scale, theme));
+        }
+        let theme = &self.themes.iter().find(|&&(s, _)| s == scale).unwrap().1;
+
+        let cursor = theme_names(cursor)
+            .iter()
+            .filter_map(|&name| theme.get_cursor(name))
+            .next()
+            .or_else(|| theme.get_cursor("left_ptr"));
+        let cursor = match cursor {
+            Some(cursor) => cursor,
+            None => return Vec::new(),
+        };
+        (0..cursor.image_count())
+            .filter_map(|i| {
+                let buffer = cursor.frame_buffer(i)?;
+                let (width, height, hotspot_x, hotspot_y, delay) = cursor.frame_info(i)?;
+                Some(Frame {
+                    buffer: (*buffer).clone(),
+                    owned: false,
+                    size: (width as i32 / scale, height as i32 / scale),
+                    hotspot: (hotspot_x as i32 / scale, hotspot_y as i32 / scale),
+                    delay: Duration::from_millis(delay as u64),
+                })
+            })
+            .collect()
+    }
+}
+
+// Some themes give their frames no delay, which would have us redraw the cursor all the time.
+const MIN_FRAME_DELAY_MS: u64 = 10;
+
+// The surface showing a window's cursor, shared with the timer animating it.
+struct CursorSurface {
+    surface: Proxy<wl_surface::WlSurface>,
+    frames: Vec<Frame>,
+    // The scale the frames were drawn at.
+    scale: i32,
+    current: usize,
+    // Bumped whenever the frames are replaced, so the animation of the old ones stops.
+    generation: u64,
+}
+
+impl CursorSurface {
+    // Attaches the frame at `index`, moving the hotspot of the pointers from the current one's.
+    fn show(&mut self, index: usize) {
+        let (dx, dy) = match (self.frames.get(self.current), self.frames.get(index)) {
+            (Some(current), Some(next)) => (current.hotspot.0 - next.hotspot.0, current.hotspot.1 - next.hotspot.1),
+            _ => (0, 0),
+        };
+        if let Some(frame) = self.frames.get(index) {
+            self.surface.attach(Some(&frame.buffer), dx, dy);
+            if self.surface.version() >= 3 {
+                self.surface.set_buffer_scale(self.scale);
+            }
+            self.surface.damage(0, 0, frame.size.0, frame.size.1);
+        }
+        self.current = index;
+    }
+}
+
+fn frame_delay(frame: &Frame) -> Duration {
+    cmp::max(frame.delay, Duration::from_millis(MIN_FRAME_DELAY_MS))
+}
+
+// Shows each frame for its delay in turn from the event loop, until the frames are replaced or the
+// cursor is dropped.
+fn animate(cursor: &Arc<Mutex<CursorSurface>>, tasks: &Mutex<LoopTasks>, generation: u64) -> u64 {
+    let delay = frame_delay(&cursor.lock().unwrap().frames[0]);
+    let cursor = Arc::downgrade(cursor);
+    tasks.lock().unwrap().add_timer(delay, move || {
+        let cursor = cursor.upgrade()?;
+        let mut state = cursor.lock().unwrap();
+        if state.generation != generation {
+            return None;
+        }
+        let next = (state.current + 1) % state.frames.len();
+        state.show(next);
+        state.surface.commit();
+        Some(frame_delay(&state.frames[next]))
+    })
+}
+
+// The cursor of a window, which the pointers show while they're over it.
+// TODO: Use `wp_cursor_shape_v1` for named cursors when the compositor offers it, once the
+// protocols we depend on include it.
+pub struct WindowCursor {
+    tasks: Arc<Mutex<LoopTasks>>,
+    shm: Proxy<wl_shm::WlShm>,
+    themes: Arc<Mutex<CursorThemes>>,
+    surface: Arc<Mutex<CursorSurface>>,
+    // The timer showing the next frame, if the cursor is animated.
+    animation: Option<u64>,
+    // Holds the images of all frames of a custom cursor. Each cursor gets a new one, as the
+    // compositor may still be reading the old one's buffers.
+    pool: Option<MemPool>,
+    cursor: Cursor,
+    hidden: bool,
+    scale: i32,
+    // The pointers over the window, with the serial of the event they entered it with.
+    pointers: Vec<(Proxy<wl_pointer::WlPointer>, u32)>,
+}
+
+impl WindowCursor {
+    pub fn new(
+        tasks: &Arc<Mutex<LoopTasks>>,
+        compositor: &Proxy<wl_compositor::WlCompositor>,
+        shm: &Proxy<wl_shm::WlShm>,
+        themes: Arc<Mutex<CursorThemes>>,
+    ) -> WindowCursor {
+        let surface = CursorSurface {
+            surface: compositor.create_surface().unwrap().implement(|_, _| ()),
+            frames: Vec::new(),
+            scale: 1,
+            current: 0,
+            generation: 0,
+        };
+        let mut window_cursor = WindowCursor {
+            tasks: tasks.clone(),
+            shm: shm.clone(),
+            themes,
+            surface: Arc::new(Mutex::new(surface)),
+            animation: None,
+            pool: None,
+            cursor: Cursor::default(),
+            hidden: false,
+            scale: 1,
+            pointers: Vec::new(),
+        };
+        window_cursor.draw();
+        window_cursor
+    }
+
+    pub fn set_cursor(&mut self, cursor: Cursor) {
+        self.cursor = cursor;
+        self.draw();
+        self.update_pointers();
+    }
+
+    pub fn reload(&mut self) {
+        self.draw();
+        self.update_pointers();
+    }
+
+    pub fn set_hidden(&mut self, hidden: bool) {
+        if hidden != self.hidden {
+            self.hidden = hidden;
+            self.update_pointers();
+        }
+    }
+
+    pub fn set_scale(&mut self, scale: i32) {
+        if scale != self.scale {
+            self.scale = scale;
+            self.draw();
+            self.update_pointers();
+        }
+    }
+
+    pub fn pointer_entered(&mut self, pointer: &Proxy<wl_pointer::WlPointer>, serial: u32) {
+        self.pointers.push((pointer.clone(), serial));
+        self.update_pointer(pointer, serial);
+    }
+
+    pub fn pointer_left(&mut self, pointer: &Proxy<wl_pointer::WlPointer>) {
+        self.pointers.retain(|&(ref p, _)| !p.equals(pointer));
+    }
+
+    // Gets the frames of the cursor at a size that's sharp on the window's outputs, and starts
+    // showing the first one.
+    fn draw(&mut self) {
+        let named = match self.cursor {
+            Cursor::Named(cursor) => Some(cursor),
+            Cursor::Custom(_) => None,
+        };
+        let (frames, pool) = match named {
+            Some(cursor) => (self.themes.lock().unwrap().frames(cursor, self.scale), None),
+            None => match self.draw_frames() {
+                Some((frames, pool)) => (frames, Some(pool)),
+                None => (Vec::new(), None),
+            },
+        };
+        if let Some(id) = self.animation.take() {
+            self.tasks.lock().unwrap().remove_timer(id);
+        }
+
+        let (old_frames, animated, generation) = {
+            let mut state = self.surface.lock().unwrap();
+            state.generation += 1;
+            let old_frames = mem::replace(&mut state.frames, frames);
+            state.scale = self.scale;
+            state.current = 0;
+            if !state.frames.is_empty() {
+                state.show(0);
+                state.surface.commit();
+            }
+            (old_frames, state.frames.len() > 1, state.generation)
+        };
+        if animated {
+            self.animation = Some(animate(&self.surface, &self.tasks, generation));
+        }
+
+        // The new buffers are attached by now. The theme's are freed along with it, and the old
+        // pool once its buffers are destroyed.
+        for frame in old_frames.into_iter().filter(|frame| frame.owned) {
+            frame.buffer.destroy();
+        }
+        self.pool = pool;
+    }
+
+    // Draws the frames of the custom cursor into new buffers, from a new pool.
+    fn draw_frames(&self) -> Option<(Vec<Frame>, MemPool)> {
+        let scale = self.scale;
+        let custom = match self.cursor {
+            Cursor::Custom(ref custom) => custom,
+            Cursor::Named(_) => return None,
+        };
+        let images: Vec<_> = custom.frames
+            .iter()
+            .map(|frame| (CursorImage::new(frame, scale as f64), frame.delay))
+            .collect();
+        let mut pool = MemPool::new(&self.shm).ok()?;
+
+        let bytes: Vec<u8> = images
+            .iter()
+            .flat_map(|&(ref image, _)| image.pixels.iter())
+            .flat_map(|&pixel| {
+                // `wl_shm` pixels are little-endian.
+                (0..4).map(move |i| (pixel >> (i * 8)) as u8)
+            })
+            .collect();
+        pool.resize(bytes.len())
+            .and_then(|()| pool.seek(SeekFrom::Start(0)))
+            .and_then(|_| pool.write_all(&bytes))
+            .and_then(|()| pool.flush())
+            .ok()?;
+
+        let mut offset = 0;
+        let frames = images
+            .into_iter()
+            .map(|(image, delay)| {
+                let buffer = pool
+                    .buffer(
+                        offset,
+                        image.width as i32,
+                        image.height as i32,
+                        image.width as i32 * 4,
+                        wl_shm::Format::Argb8888,
+                    )
+                    .implement(|_, _| ());
+                offset += image.pixels.len() as i32 * 4;
+                Frame {
+                    buffer,
+                    owned: true,
+                    size: (image.width as i32 / scale, image.height as i32 / scale),
+                    hotspot: (image.hotspot_x as i32 / scale, image.hotspot_y as i32 / scale),
+                    delay,
+                }
+            })
+            .collect();
+        Some((frames, pool))
+    }
+
+    fn update_pointers(&self) {
+        for &(ref pointer, serial) in &self.pointers {
+            self.update_pointer(pointer, serial);
+        }
+    }
+
+    fn update_pointer(&self, pointer: &Proxy<wl_pointer::WlPointer>, serial: u32) {
+        if self.hidden {
+            pointer.set_cursor(serial, None, 0, 0);
+            return;
+        }
+        let state = self.surface.lock().unwrap();
+        if let Some(frame) = state.frames.get(state.current) {
+            pointer.set_cursor(serial, Some(&state.surface), frame.hotspot.0, frame.hotspot.1);
+        }
+    }
+}
+
+impl Drop for WindowCursor {
+    fn drop(&mut self) {
+        if let Some(id) = self.animation.take() {
+            self.tasks.lock().unwrap().remove_timer(id);
+        }
+        let mut state = self.surface.lock().unwrap();
+        // The animation would otherwise show another frame before the surface is dropped.
+        state.generation += 1;
+        for frame in state.frames.drain(..).filter(|frame| frame.owned) {
+            frame.buffer.destroy();
+        }
+        state.surface.destroy();
+    }
+}
@@ -0,0 +1,485 @@
+use std::os::unix::io::RawFd;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use {DndAction, WindowEvent};
+
+use sctk::reexports::client::{Display, NewProxy, Proxy};
+use sctk::reexports::client::protocol::{
+    wl_data_device,
+    wl_data_device_manager,
+    wl_data_offer,
+    wl_data_source,
+    wl_seat,
+    wl_surface,
+};
+use sctk::reexports::client::protocol::wl_data_device::RequestsTrait as DataDeviceRequests;
+use sctk::reexports::client::protocol::wl_data_device_manager::RequestsTrait as DataDeviceManagerRequests;
+use sctk::reexports::client::protocol::wl_data_offer::RequestsTrait as DataOfferRequests;
+use sctk::reexports::client::protocol::wl_data_source::RequestsTrait as DataSourceRequests;
+
+use super::{make_wid, EventsLoopSink, WindowId};
+use super::selection::{
+    send_through_pipe,
+    DeviceState,
+    PipeReader,
+    Selection,
+    SelectionOffer,
+    SelectionProtocol,
+};
+use super::tasks::LoopTasks;
+use super::window::WindowStore;
+use super::super::clipboard::{self, ClipboardContents};
+use super::super::dnd::{parse_uri_list, DropResponse};
+
+type DataOffer = SelectionOffer<DataDevice>;
+
+// A drag over one of our windows that offers something we accept.
+struct Drag {
+    offer: DataOffer,
+    wid: WindowId,
+    // The dropped files, if the drag offers `text/uri-list`.
+    paths: Vec<PathBuf>,
+    // The `text/uri-list` of the drag while it's being sent, which we read as it arrives so we
+    // don't hold up the event loop.
+    uri_list: Option<PipeReader>,
+    // The first type accepted by the window that the drag offers.
+    data_type: Option<String>,
+    drop_target: Arc<Mutex<DropTarget>>,
+}
+
+impl Drag {
+    // Reads as much of the `text/uri-list` as has arrived, and sends `HoveredFile` for each file
+    // once it's complete.
+    fn read_paths(&mut self, sink: &mut EventsLoopSink) {
+        let result = match self.uri_list {
+            Some(ref mut reader) => reader.read(0),
+            None => return,
+        };
+        match result {
+            Ok(None) => return,
+            Ok(Some(data)) => self.set_paths(&data, sink),
+            // The drag source stopped responding, so there's no telling what the files are.
+            Err(_) => (),
+        }
+        self.uri_list = None;
+    }
+
+    fn set_paths(&mut self, uri_list: &[u8], sink: &mut EventsLoopSink) {
+        self.paths = parse_uri_list(uri_list).unwrap_or_default();
+        for path in &self.paths {
+            sink.send_event(WindowEvent::HoveredFile(path.clone()), self.wid);
+        }
+    }
+}
+
+// What can be dropped into a window, shared between the window and the data devices.
+#[derive(Default)]
+pub struct DropTarget {
+    pub accepted_types: Vec<String>,
+    response: DropResponse,
+    // The drag over the window with its enter serial and the type we accept it as, which a
+    // changed response applies to right away.
+    offer: Option<(Proxy<wl_data_offer::WlDataOffer>, u32, String)>,
+}
+
+impl DropTarget {
+    pub fn set_response(&mut self, response: DropResponse) {
+        self.response = response;
+        self.send_response();
+    }
+
+    fn send_response(&self) {
+        let (offer, serial, mime_type) = match self.offer {
+            Some((ref offer, serial, ref mime_type)) => (offer, serial, mime_type),
+            None => return,
+        };
+        // There's nothing like a link action, so we copy instead. Versions before 3 can only copy.
+        let action = match self.response.action {
+            Some(DndAction::Move) => wl_data_device_manager::DndAction::Move,
+            Some(DndAction::Copy) | Some(DndAction::Link) => wl_data_device_manager::DndAction::Copy,
+            None => wl_data_device_manager::DndAction::empty(),
+        };
+        if action.is_empty() {
+            offer.accept(serial, None);
+        } else {
+            offer.accept(serial, Some(mime_type.clone()));
+        }
+        if offer.version() >= 3 {
+            offer.set_actions(action, action);
+        }
+    }
+}
+
+// The contents of a drag we started, while it's in progress.
+type OwnDrag = Arc<Mutex<Option<Arc<ClipboardContents>>>>;
+
+// The offer of a drop, which is finished once everything we read from it has arrived.
+struct DroppedOffer(DataOffer);
+
+impl Drop for DroppedOffer {
+    fn drop(&mut self) {
+        if self.0.offer.version() >= 3 {
+            self.0.offer.finish();
+        }
+    }
+}
+
+// Sends the dropped files and data once they've been read, without blocking the event loop. The
+// offer is finished once the last of them has arrived.
+fn receive_drop(
+    drag: Drag,
+    display: &Display,
+    own_drag: &OwnDrag,
+    tasks: &Mutex<LoopTasks>,
+    sink: &mut EventsLoopSink,
+) {
+    let wid = drag.wid;
+    let offer = Arc::new(DroppedOffer(drag.offer));
+    let mut tasks = tasks.lock().unwrap();
+
+    match drag.uri_list {
+        Some(reader) => {
+            let mut offer = Some(offer.clone());
+            tasks.read_pipe(reader, move |uri_list, sink| {
+                for path in parse_uri_list(&uri_list.unwrap_or_default()).unwrap_or_default() {
+                    sink.send_event(WindowEvent::DroppedFile(path), wid);
+                }
+                offer.take();
+            });
+        }
+        None => for path in drag.paths {
+            sink.send_event(WindowEvent::DroppedFile(path), wid);
+        },
+    }
+
+    let mime_type = match drag.data_type {
+        Some(mime_type) => mime_type,
+        None => return,
+    };
+    // We'd have to answer our own request, which we can't while we wait for it.
+    if let Some(ref contents) = *own_drag.lock().unwrap() {
+        if let Some(data) = clipboard::find_data(contents, &[mime_type.as_str()]) {
+            sink.send_event(WindowEvent::DroppedData { mime_type, data }, wid);
+        }
+        return;
+    }
+    if let Some(reader) = offer.0.start_receive(display, &[mime_type.as_str()]) {
+        let mut offer = Some(offer.clone());
+        let mut mime_type = Some(mime_type);
+        tasks.read_pipe(reader, move |data, sink| {
+            if let (Some(mime_type), Some(data)) = (mime_type.take(), data) {
+                sink.send_event(WindowEvent::DroppedData { mime_type, data }, wid);
+            }
+            offer.take();
+        });
+    }
+}
+
+fn implement_data_device(
+    device: NewProxy<wl_data_device::WlDataDevice>,
+    state: Arc<Mutex<DeviceState<DataDevice>>>,
+    display: Arc<Display>,
+    sink: Arc<Mutex<EventsLoopSink>>,
+    store: Arc<Mutex<WindowStore>>,
+    own_drag: OwnDrag,
+    tasks: Arc<Mutex<LoopTasks>>,
+) -> Proxy<wl_data_device::WlDataDevice> {
+    // The drag over one of our windows, if it offers something the window accepts.
+    let mut drag: Option<Drag> = None;
+    device.implement(move |evt, _| {
+        let mut state = state.lock().unwrap();
+        let mut sink = sink.lock().unwrap();
+        match evt {
+            wl_data_device::Event::DataOffer { id } => state.add_offer(id),
+            wl_data_device::Event::Selection { id } => state.set_selection(id),
+            wl_data_device::Event::Enter { serial, surface, x, y, id } => {
+                // Dropping the offer destroys it, which also rejects it.
+                let offer = match id.and_then(|id| state.take_offer(&id)) {
+                    Some(offer) => offer,
+                    None => return,
+                };
+                let drop_target = match store.lock().unwrap().find_drop_target(&surface) {
+                    Some(drop_target) => drop_target,
+                    None => return,
+                };
+                let mut target = drop_target.lock().unwrap();
+                let (has_files, data_type) = {
+                    let offered = offer.mime_types.lock().unwrap();
+                    (
+                        offered.iter().any(|m| m == "text/uri-list"),
+                        target.accepted_types.iter().find(|&t| offered.contains(t)).cloned(),
+                    )
+                };
+                if !has_files && data_type.is_none() {
+                    offer.offer.accept(serial, None);
+                    return;
+                }
+
+                let mime_type = match data_type {
+                    Some(ref data_type) if !has_files => data_type.clone(),
+                    _ => "text/uri-list".to_owned(),
+                };
+                // Each drag starts out accepted, until the application answers otherwise.
+                target.offer = Some((offer.offer.clone(), serial, mime_type));
+                target.set_response(DropResponse::default());
+                drop(target);
+
+                let wid = make_wid(&surface);
+                let mut new_drag = Drag {
+                    offer,
+                    wid,
+                    paths: Vec::new(),
+                    uri_list: None,
+                    data_type,
+                    drop_target,
+                };
+                if has_files {
+                    // Unlike X11, the data is available as soon as the drag enters. We'd have to
+                    // answer our own request, which we can't while we wait for it.
+                    let own_uri_list = own_drag
+                        .lock()
+                        .unwrap()
+                        .as_ref()
+                        .map(|contents| clipboard::find_data(contents, &["text/uri-list"]));
+                    match own_uri_list {
+                        Some(data) => new_drag.set_paths(&data.unwrap_or_default(), &mut sink),
+                        None => {
+                            new_drag.uri_list = new_drag.offer.start_receive(&display, &["text/uri-list"]);
+                            new_drag.read_paths(&mut sink);
+                        }
+                    }
+                }
+                sink.send_event(WindowEvent::DragMoved { position: (x, y).into() }, wid);
+                drag = Some(new_drag);
+            }
+            wl_data_device::Event::Motion { x, y, .. } => {
+                if let Some(ref mut drag) = drag {
+                    drag.read_paths(&mut sink);
+                    sink.send_event(WindowEvent::DragMoved { position: (x, y).into() }, drag.wid);
+                }
+            }
+            wl_data_device::Event::Leave => {
+                if let Some(drag) = drag.take() {
+                    drag.drop_target.lock().unwrap().offer = None;
+                    sink.send_event(WindowEvent::HoveredFileCancelled, drag.wid);
+                }
+            }
+            wl_data_device::Event::Drop => {
+                if let Some(drag) = drag.take() {
+                    let accepted = {
+                        let mut target = drag.drop_target.lock().unwrap();
+                        target.offer = None;
+                        target.response.action.is_some()
+                    };
+                    // Compositors older than version 3 don't know we rejected the drop.
+                    if !accepted {
+                        sink.send_event(WindowEvent::HoveredFileCancelled, drag.wid);
+                        return;
+                    }
+                    receive_drop(drag, &display, &own_drag, &tasks, &mut sink);
+                }
+            }
+        }
+    })
+}
+
+// The selection protocol of the core data device manager.
+//
+// This also receives drag and drop, which goes through the same data devices.
+pub struct DataDevice {
+    display: Arc<Display>,
+    sink: Arc<Mutex<EventsLoopSink>>,
+    store: Arc<Mutex<WindowStore>>,
+    own_drag: OwnDrag,
+    tasks: Arc<Mutex<LoopTasks>>,
+}
+
+impl DataDevice {
+    pub fn new(
+        display: Arc<Display>,
+        sink: Arc<Mutex<EventsLoopSink>>,
+        store: Arc<Mutex<WindowStore>>,
+        tasks: Arc<Mutex<LoopTasks>>,
+    ) -> DataDevice {
+        DataDevice {
+            display,
+            sink,
+            store,
+            own_drag: Arc::new(Mutex::new(None)),
+            tasks,
+        }
+    }
+}
+
+impl SelectionProtocol for DataDevice {
+    type Manager = wl_data_device_manager::WlDataDeviceManager;
+    type Device = wl_data_device::WlDataDevice;
+    type Offer = wl_data_offer::WlDataOffer;
+    type Source = wl_data_source::WlDataSource;
+
+    fn get_device(
+        manager: &Proxy<Self::Manager>,
+        seat: &Proxy<wl_seat::WlSeat>,
+    ) -> NewProxy<Self::Device> {
+        manager.get_data_device(seat).unwrap()
+    }
+
+    fn implement_device(
+        &self,
+        device: NewProxy<Self::Device>,
+        state: Arc<Mutex<DeviceState<Self>>>,
+    ) -> Proxy<Self::Device> {
+        implement_data_device(
+            device,
+            state,
+            self.display.clone(),
+            self.sink.clone(),
+            self.store.clone(),
+            self.own_drag.clone(),
+            self.tasks.clone(),
+        )
+    }
+
+    fn destroy_device(device: &Proxy<Self::Device>) {
+        if device.version() >= 2 {
+            device.release();
+        }
+    }
+
+    fn set_selection(device: &Proxy<Self::Device>, source: &Proxy<Self::Source>, serial: u32) {
+        device.set_selection(Some(source), serial);
+    }
+
+    fn implement_offer(
+        offer: NewProxy<Self::Offer>,
+        mime_types: Arc<Mutex<Vec<String>>>,
+    ) -> Proxy<Self::Offer> {
+        offer.implement(move |evt, _| match evt {
+            wl_data_offer::Event::Offer { mime_type } => {
+                mime_types.lock().unwrap().push(mime_type);
+            }
+            _ => (),
+        })
+    }
+
+    fn receive(offer: &Proxy<Self::Offer>, mime_type: String, fd: RawFd) {
+        offer.receive(mime_type, fd);
+    }
+
+    fn destroy_offer(offer: &Proxy<Self::Offer>) {
+        offer.destroy();
+    }
+
+    fn create_source(manager: &Proxy<Self::Manager>) -> NewProxy<Self::Source> {
+        manager.create_data_source().unwrap()
+    }
+
+    fn implement_source<F>(
+        source: NewProxy<Self::Source>,
+        contents: Arc<ClipboardContents>,
+        mut cancelled: F,
+    ) -> Proxy<Self::Source>
+    where
+        F: FnMut(&Proxy<Self::Source>) + Send + 'static,
+    {
+        source.implement(move |evt, source: Proxy<wl_data_source::WlDataSource>| match evt {
+            wl_data_source::Event::Send { mime_type, fd } => {
+                // We still close the pipe if we were asked for a type we didn't offer.
+                let data = clipboard::find_data(&contents, &[&mime_type]);
+                send_through_pipe(fd, &data.unwrap_or_default());
+            }
+            wl_data_source::Event::Cancelled => {
+                cancelled(&source);
+                source.destroy();
+            }
+            _ => (),
+        })
+    }
+
+    fn offer(source: &Proxy<Self::Source>, mime_type: String) {
+        source.offer(mime_type);
+    }
+}
+
+// The clipboard, shared between the event loop and the windows.
+pub type Clipboard = Selection<DataDevice>;
+
+impl Clipboard {
+    pub fn start_drag(
+        &mut self,
+        surface: &Proxy<wl_surface::WlSurface>,
+        wid: WindowId,
+        seat_id: u32,
+        serial: u32,
+        contents: ClipboardContents,
+        actions: &[DndAction],
+    ) -> Result<(), String> {
+        let manager = match self.manager {
+            Some(ref manager) => manager,
+            None => return Err("Drag could not be started: no data device manager".to_owned()),
+        };
+        // The drag has to be started from the seat whose button press started the implicit grab.
+        let device = match self.devices.iter().find(|device| device.seat_id == seat_id) {
+            Some(device) => device,
+            None => return Err("Drag could not be started: the seat has no data device".to_owned()),
+        };
+        let contents = Arc::new(contents);
+        let source = manager.create_data_source().unwrap().implement({
+            let own_drag = self.protocol.own_drag.clone();
+            let sink = self.protocol.sink.clone();
+            let contents = contents.clone();
+            let mut action = None;
+            move |evt, source: Proxy<wl_data_source::WlDataSource>| match evt {
+                wl_data_source::Event::Send { mime_type, fd } => {
+                    let data = clipboard::find_data(&contents, &[&mime_type]);
+                    send_through_pipe(fd, &data.unwrap_or_default());
+                }
+                wl_data_source::Event::Action { dnd_action } => {
+                    action = if dnd_action.contains(wl_data_device_manager::DndAction::Move) {
+                        Some(DndAction::Move)
+                    } else if dnd_action.contains(wl_data_device_manager::DndAction::Copy) {
+                        Some(DndAction::Copy)
+                    } else {
+                        None
+                    };
+                }
+                wl_data_source::Event::Cancelled => {
+                    *own_drag.lock().unwrap() = None;
+                    sink.lock().unwrap().send_event(WindowEvent::DragEnded { action: None }, wid);
+                    source.destroy();
+                }
+                wl_data_source::Event::DndFinished => {
+                    *own_drag.lock().unwrap() = None;
+                    // Version 3 sources are always told the action before the drop is finished.
+                    let action = action.or(Some(DndAction::Copy));
+                    sink.lock().unwrap().send_event(WindowEvent::DragEnded { action }, wid);
+                    source.destroy();
+                }
+                _ => (),
+            }
+        });
+        for &(ref mime_type, _) in contents.iter() {
+            source.offer(mime_type.clone());
+        }
+        if source.version() >= 3 {
+            let dnd_actions = actions.iter().fold(
+                wl_data_device_manager::DndAction::empty(),
+                |dnd_actions, &action| dnd_actions | match action {
+                    DndAction::Copy => wl_data_device_manager::DndAction::Copy,
+                    DndAction::Move => wl_data_device_manager::DndAction::Move,
+                    // Wayland has no equivalent.
+                    DndAction::Link => wl_data_device_manager::DndAction::empty(),
+                },
+            );
+            if dnd_actions.is_empty() {
+                source.set_actions(wl_data_device_manager::DndAction::Copy);
+            } else {
+                source.set_actions(dnd_actions);
+            }
+        }
+        *self.protocol.own_drag.lock().unwrap() = Some(contents);
+        device.device.start_drag(Some(&source), surface, None, serial);
+        Ok(())
+    }
+}
@@ -3,20 +3,37 @@ use std::collections::VecDeque;
 use std::fmt;
 use std::sync::{Arc, Mutex, Weak};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
-use {ControlFlow, EventsLoopClosed, PhysicalPosition, PhysicalSize};
+use {ControlFlow, EventsLoopClosed, ModifiersState, PhysicalPosition, PhysicalSize, PointerState};
 
 use super::WindowId;
 use super::window::WindowStore;
+use super::cursor::CursorThemes;
+use super::pointer::PointerTracker;
+use super::pointer_gestures::PointerGestures;
+use super::super::cursor::ThemeOverride;
+use super::data_device::{Clipboard, DataDevice};
+use super::pointer_constraints::Pointers;
+use super::primary_selection::{PrimarySelection, PrimarySelectionDevice};
+use super::tasks::{self, LoopTasks};
 
 use sctk::Environment;
 use sctk::output::OutputMgr;
 use sctk::reexports::client::{Display, EventQueue, GlobalEvent, Proxy, ConnectError};
 use sctk::reexports::client::commons::Implementation;
-use sctk::reexports::client::protocol::{wl_keyboard, wl_output, wl_pointer, wl_registry, wl_seat,
-                                        wl_touch};
+use sctk::reexports::client::protocol::{wl_data_device_manager, wl_keyboard, wl_output, wl_pointer,
+                                        wl_registry, wl_seat, wl_touch};
 
 use sctk::reexports::client::protocol::wl_display::RequestsTrait as DisplayRequests;
+use sctk::reexports::protocols::unstable::pointer_constraints::v1::client::zwp_pointer_constraints_v1;
+use sctk::reexports::protocols::unstable::pointer_gestures::v1::client::zwp_pointer_gestures_v1;
+use sctk::reexports::protocols::unstable::primary_selection::v1::client::zwp_primary_selection_device_manager_v1;
+use sctk::reexports::protocols::unstable::relative_pointer::v1::client::zwp_relative_pointer_manager_v1;
+use sctk::reexports::protocols::unstable::text_input::v3::client::{
+    zwp_text_input_manager_v3,
+    zwp_text_input_v3,
+};
 
 pub struct EventsLoopSink {
     buffer: VecDeque<::Event>,
@@ -68,6 +85,18 @@ pub struct EventsLoop {
     pub display: Arc<Display>,
     // The list of seats
     pub seats: Arc<Mutex<Vec<(u32, Proxy<wl_seat::WlSeat>)>>>,
+    // The clipboard, shared with the windows
+    pub clipboard: Arc<Mutex<Clipboard>>,
+    // The primary selection, shared with the windows
+    pub primary_selection: Arc<Mutex<PrimarySelection>>,
+    // The cursor themes, shared with the windows
+    pub cursor_themes: Arc<Mutex<CursorThemes>>,
+    // The pointers of all seats, shared with the windows to grab them
+    pub pointers: Arc<Mutex<Pointers>>,
+    // The state of the pointers, shared with the windows
+    pub pointer_tracker: Arc<Mutex<PointerTracker>>,
+    // What we wait for besides Wayland events
+    pub tasks: Arc<Mutex<LoopTasks>>,
 }
 
 // A handle that can be sent across threads and used to wake up the `EventsLoop`.
@@ -103,10 +132,18 @@ impl EventsLoopProxy {
 impl EventsLoop {
     pub fn new() -> Result<EventsLoop, ConnectError> {
         let (display, mut event_queue) = Display::connect_to_env()?;
+        let display = Arc::new(display);
 
         let sink = Arc::new(Mutex::new(EventsLoopSink::new()));
         let store = Arc::new(Mutex::new(WindowStore::new()));
         let seats = Arc::new(Mutex::new(Vec::new()));
+        let tasks = Arc::new(Mutex::new(LoopTasks::new(display.clone())));
+        let data_device = DataDevice::new(display.clone(), sink.clone(), store.clone(), tasks.clone());
+        let clipboard = Arc::new(Mutex::new(Clipboard::new(display.clone(), data_device)));
+        let primary_selection = PrimarySelection::new(display.clone(), PrimarySelectionDevice);
+        let primary_selection = Arc::new(Mutex::new(primary_selection));
+        let pointers = Arc::new(Mutex::new(Pointers::new(sink.clone())));
+        let pointer_tracker = Arc::new(Mutex::new(PointerTracker::default()));
 
         let env = Environment::from_registry_with_cb(
             display.get_registry().unwrap(),
@@ -115,11 +152,20 @@ impl EventsLoop {
                 sink: sink.clone(),
                 store: store.clone(),
                 seats: seats.clone(),
+                clipboard: clipboard.clone(),
+                primary_selection: primary_selection.clone(),
+                pointers: pointers.clone(),
+                pointer_tracker: pointer_tracker.clone(),
+                gestures: Arc::new(Mutex::new(PointerGestures::new(sink.clone(), store.clone()))),
+                text_input_manager: None,
+                text_inputs: Vec::new(),
             },
         ).unwrap();
 
+        let cursor_themes = Arc::new(Mutex::new(CursorThemes::new(&env.shm)));
+
         Ok(EventsLoop {
-            display: Arc::new(display),
+            display: display,
             evq: RefCell::new(event_queue),
             sink: sink,
             pending_wakeup: Arc::new(AtomicBool::new(false)),
@@ -127,9 +173,38 @@ impl EventsLoop {
             env: env,
             cleanup_needed: Arc::new(Mutex::new(false)),
             seats: seats,
+            clipboard: clipboard,
+            primary_selection: primary_selection,
+            cursor_themes: cursor_themes,
+            pointers: pointers,
+            pointer_tracker: pointer_tracker,
+            tasks: tasks,
+        })
+    }
+
+    pub fn get_pointer_state(&self) -> Option<PointerState> {
+        let tracker = self.pointer_tracker.lock().unwrap();
+        Some(PointerState {
+            // Clients aren't told where the pointer is, outside of their surfaces.
+            root_position: None,
+            window: tracker.focus().map(|(wid, position)| {
+                (::WindowId(::platform::WindowId::Wayland(wid)), position.into())
+            }),
+            buttons: tracker.buttons(),
+            // TODO: replace dummy value with actual modifier state
+            modifiers: ModifiersState::default(),
         })
     }
 
+    pub fn set_cursor_theme(&self, theme: Option<&str>, size: Option<u32>) {
+        let old_themes = self.cursor_themes
+            .lock()
+            .unwrap()
+            .set_theme(ThemeOverride { name: theme.map(str::to_owned), size });
+        self.store.lock().unwrap().reload_cursors();
+        drop(old_themes);
+    }
+
     pub fn create_proxy(&self) -> EventsLoopProxy {
         EventsLoopProxy {
             display: Arc::downgrade(&self.display),
@@ -147,15 +222,7 @@ impl EventsLoop {
         // dispatch any pre-buffered events
         self.sink.lock().unwrap().empty_with(&mut callback);
 
-        // try to read pending events
-        if let Some(h) = self.evq.get_mut().prepare_read() {
-            h.read_events().expect("Wayland connection lost.");
-        }
-        // dispatch wayland events
-        self.evq
-            .get_mut()
-            .dispatch_pending()
-            .expect("Wayland connection lost.");
+        self.dispatch(false);
         self.post_dispatch_triggers();
 
         // dispatch buffered events to client
@@ -183,10 +250,7 @@ impl EventsLoop {
 
         loop {
             // dispatch events blocking if needed
-            self.evq
-                .get_mut()
-                .dispatch()
-                .expect("Wayland connection lost.");
+            self.dispatch(true);
             self.post_dispatch_triggers();
 
             // empty buffer of events
@@ -212,6 +276,26 @@ impl EventsLoop {
  */
 
 impl EventsLoop {
+    // Reads and dispatches the Wayland events, and runs the tasks that are ready. If `block` is
+    // set, this waits until there's something to do.
+    fn dispatch(&mut self, block: bool) {
+        let evq = self.evq.get_mut();
+        evq.dispatch_pending().expect("Wayland connection lost.");
+        self.display.flush().expect("Wayland connection lost.");
+        // This fails if events were queued in the meantime, which we dispatch right away.
+        if let Some(guard) = evq.prepare_read() {
+            let (mut fds, timeout) = self.tasks.lock().unwrap().poll_fds();
+            fds.insert(0, self.display.get_connection_fd());
+            let timeout = if block { timeout } else { Some(Duration::from_millis(0)) };
+            if tasks::poll(&fds, timeout)[0] {
+                guard.read_events().expect("Wayland connection lost.");
+            }
+            // Otherwise, dropping the guard cancels the read.
+        }
+        evq.dispatch_pending().expect("Wayland connection lost.");
+        tasks::run_tasks(&self.tasks, &self.sink);
+    }
+
     fn post_dispatch_triggers(&mut self) {
         let mut sink = self.sink.lock().unwrap();
         // process a possible pending wakeup call
@@ -266,12 +350,35 @@ struct SeatManager {
     sink: Arc<Mutex<EventsLoopSink>>,
     store: Arc<Mutex<WindowStore>>,
     seats: Arc<Mutex<Vec<(u32, Proxy<wl_seat::WlSeat>)>>>,
+    clipboard: Arc<Mutex<Clipboard>>,
+    primary_selection: Arc<Mutex<PrimarySelection>>,
+    pointers: Arc<Mutex<Pointers>>,
+    pointer_tracker: Arc<Mutex<PointerTracker>>,
+    gestures: Arc<Mutex<PointerGestures>>,
+    text_input_manager: Option<Proxy<zwp_text_input_manager_v3::ZwpTextInputManagerV3>>,
+    // One text input per seat, keyed by the seat's global id
+    text_inputs: Vec<(u32, Proxy<zwp_text_input_v3::ZwpTextInputV3>)>,
+}
+
+impl SeatManager {
+    fn new_text_input(&mut self, id: u32, seat: &Proxy<wl_seat::WlSeat>) {
+        use self::zwp_text_input_manager_v3::RequestsTrait as TextInputManagerRequests;
+        if let Some(ref manager) = self.text_input_manager {
+            let text_input = super::text_input::implement_text_input(
+                manager.get_text_input(seat).unwrap(),
+                self.sink.clone(),
+                self.store.clone(),
+            );
+            self.text_inputs.push((id, text_input));
+        }
+    }
 }
 
 impl Implementation<Proxy<wl_registry::WlRegistry>, GlobalEvent> for SeatManager {
     fn receive(&mut self, evt: GlobalEvent, registry: Proxy<wl_registry::WlRegistry>) {
         use self::wl_registry::RequestsTrait as RegistryRequests;
         use self::wl_seat::RequestsTrait as SeatRequests;
+        use self::zwp_text_input_v3::RequestsTrait as TextInputRequests;
         match evt {
             GlobalEvent::New {
                 id,
@@ -280,20 +387,116 @@ impl Implementation<Proxy<wl_registry::WlRegistry>, GlobalEvent> for SeatManager
             } if interface == "wl_seat" =>
             {
                 use std::cmp::min;
+                let serial = Arc::new(Mutex::new(0));
                 let seat = registry
                     .bind::<wl_seat::WlSeat>(min(version, 5), id)
                     .unwrap()
                     .implement(SeatData {
+                        id,
                         sink: self.sink.clone(),
                         store: self.store.clone(),
+                        serial: serial.clone(),
+                        pointers: self.pointers.clone(),
+                        pointer_tracker: self.pointer_tracker.clone(),
+                        gestures: self.gestures.clone(),
                         pointer: None,
                         keyboard: None,
                         touch: None,
                     });
                 self.store.lock().unwrap().new_seat(&seat);
+                self.clipboard.lock().unwrap().new_seat(id, &seat, serial.clone());
+                self.primary_selection.lock().unwrap().new_seat(id, &seat, serial);
+                self.new_text_input(id, &seat);
                 self.seats.lock().unwrap().push((id, seat));
             }
+            GlobalEvent::New {
+                id,
+                ref interface,
+                version,
+            } if interface == "wl_data_device_manager" =>
+            {
+                use std::cmp::min;
+                let manager = registry
+                    .bind::<wl_data_device_manager::WlDataDeviceManager>(min(version, 3), id)
+                    .unwrap()
+                    .implement(|_, _| {});
+                self.clipboard.lock().unwrap().set_manager(manager);
+            }
+            GlobalEvent::New {
+                id,
+                ref interface,
+                ..
+            } if interface == "zwp_primary_selection_device_manager_v1" =>
+            {
+                let manager = registry
+                    .bind::<zwp_primary_selection_device_manager_v1::ZwpPrimarySelectionDeviceManagerV1>(
+                        1,
+                        id,
+                    )
+                    .unwrap()
+                    .implement(|_, _| {});
+                self.primary_selection.lock().unwrap().set_manager(manager);
+            }
+            GlobalEvent::New {
+                id,
+                ref interface,
+                ..
+            } if interface == "zwp_pointer_constraints_v1" =>
+            {
+                let constraints = registry
+                    .bind::<zwp_pointer_constraints_v1::ZwpPointerConstraintsV1>(1, id)
+                    .unwrap()
+                    .implement(|_, _| {});
+                self.pointers.lock().unwrap().set_constraints(constraints);
+            }
+            GlobalEvent::New {
+                id,
+                ref interface,
+                ..
+            } if interface == "zwp_relative_pointer_manager_v1" =>
+            {
+                let manager = registry
+                    .bind::<zwp_relative_pointer_manager_v1::ZwpRelativePointerManagerV1>(1, id)
+                    .unwrap()
+                    .implement(|_, _| {});
+                self.pointers.lock().unwrap().set_relative_manager(manager);
+            }
+            GlobalEvent::New {
+                id,
+                ref interface,
+                ..
+            } if interface == "zwp_pointer_gestures_v1" =>
+            {
+                let manager = registry
+                    .bind::<zwp_pointer_gestures_v1::ZwpPointerGesturesV1>(1, id)
+                    .unwrap()
+                    .implement(|_, _| {});
+                self.gestures.lock().unwrap().set_manager(manager);
+            }
+            GlobalEvent::New {
+                id,
+                ref interface,
+                ..
+            } if interface == "zwp_text_input_manager_v3" =>
+            {
+                self.text_input_manager = Some(
+                    registry
+                        .bind::<zwp_text_input_manager_v3::ZwpTextInputManagerV3>(1, id)
+                        .unwrap()
+                        .implement(|_, _| {}),
+                );
+                let seats = self.seats.lock().unwrap().clone();
+                for (seat_id, seat) in seats {
+                    self.new_text_input(seat_id, &seat);
+                }
+            }
             GlobalEvent::Removed { id, ref interface } if interface == "wl_seat" => {
+                if let Some(idx) = self.text_inputs.iter().position(|&(i, _)| i == id) {
+                    let (_, text_input) = self.text_inputs.swap_remove(idx);
+                    text_input.destroy();
+                }
+                self.clipboard.lock().unwrap().remove_seat(id);
+                self.primary_selection.lock().unwrap().remove_seat(id);
                 let mut seats = self.seats.lock().unwrap();
                 if let Some(idx) = seats.iter().position(|&(i, _)| i == id) {
                     let (_, seat) = seats.swap_remove(idx);
@@ -308,8 +511,15 @@ impl Implementation<Proxy<wl_registry::WlRegistry>, GlobalEvent> for SeatManager
 }
 
 struct SeatData {
+    // The name of the seat's global
+    id: u32,
     sink: Arc<Mutex<EventsLoopSink>>,
     store: Arc<Mutex<WindowStore>>,
+    // The serial of the last input event on this seat
+    serial: Arc<Mutex<u32>>,
+    pointers: Arc<Mutex<Pointers>>,
+    pointer_tracker: Arc<Mutex<PointerTracker>>,
+    gestures: Arc<Mutex<PointerGestures>>,
     pointer: Option<Proxy<wl_pointer::WlPointer>>,
     keyboard: Option<Proxy<wl_keyboard::WlKeyboard>>,
     touch: Option<Proxy<wl_touch::WlTouch>>,
@@ -323,15 +533,24 @@ impl Implementation<Proxy<wl_seat::WlSeat>, wl_seat::Event> for SeatData {
             wl_seat::Event::Capabilities { capabilities } => {
                 // create pointer if applicable
                 if capabilities.contains(wl_seat::Capability::Pointer) && self.pointer.is_none() {
-                    self.pointer = Some(super::pointer::implement_pointer(
+                    let pointer = super::pointer::implement_pointer(
                         seat.get_pointer().unwrap(),
                         self.sink.clone(),
                         self.store.clone(),
-                    ))
+                        self.serial.clone(),
+                        self.pointer_tracker.clone(),
+                        self.id,
+                    );
+                    self.gestures.lock().unwrap().add_pointer(&pointer);
+                    let mut pointers = self.pointers.lock().unwrap();
+                    pointers.add_pointer(&pointer);
+                    self.store.lock().unwrap().new_pointer(&pointer, &pointers);
+                    self.pointer = Some(pointer);
                 }
                 // destroy pointer if applicable
                 if !capabilities.contains(wl_seat::Capability::Pointer) {
                     if let Some(pointer) = self.pointer.take() {
+                        self.remove_pointer(&pointer);
                         if pointer.version() >= 3 {
                             use self::wl_pointer::RequestsTrait;
                             pointer.release();
@@ -343,6 +562,7 @@ impl Implementation<Proxy<wl_seat::WlSeat>, wl_seat::Event> for SeatData {
                     self.keyboard = Some(super::keyboard::init_keyboard(
                         seat.get_keyboard().unwrap(),
                         self.sink.clone(),
+                        self.serial.clone(),
                     ))
                 }
                 // destroy keyboard if applicable
@@ -376,9 +596,19 @@ impl Implementation<Proxy<wl_seat::WlSeat>, wl_seat::Event> for SeatData {
     }
 }
 
+impl SeatData {
+    fn remove_pointer(&self, pointer: &Proxy<wl_pointer::WlPointer>) {
+        self.store.lock().unwrap().remove_pointer(pointer);
+        self.pointers.lock().unwrap().remove_pointer(pointer);
+        self.gestures.lock().unwrap().remove_pointer(pointer);
+        self.pointer_tracker.lock().unwrap().remove_seat(self.id);
+    }
+}
+
 impl Drop for SeatData {
     fn drop(&mut self) {
         if let Some(pointer) = self.pointer.take() {
+            self.remove_pointer(&pointer);
             if pointer.version() >= 3 {
                 use self::wl_pointer::RequestsTrait;
                 pointer.release();
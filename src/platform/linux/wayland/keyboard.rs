@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use {ElementState, KeyboardInput, ModifiersState, VirtualKeyCode, WindowEvent};
 
 use super::{make_wid, DeviceId, EventsLoopSink};
+use super::super::keysym::keysym_to_vkey;
 use sctk::keyboard::{self, map_keyboard_auto, Event as KbEvent};
 use sctk::reexports::client::{NewProxy, Proxy};
 use sctk::reexports::client::protocol::wl_keyboard;
@@ -10,13 +11,16 @@ use sctk::reexports::client::protocol::wl_keyboard;
 pub fn init_keyboard(
     keyboard: NewProxy<wl_keyboard::WlKeyboard>,
     sink: Arc<Mutex<EventsLoopSink>>,
+    serial: Arc<Mutex<u32>>,
 ) -> Proxy<wl_keyboard::WlKeyboard> {
     // { variables to be captured by the closure
     let mut target = None;
     let my_sink = sink.clone();
+    let my_serial = serial.clone();
     // }
     let ret = map_keyboard_auto(keyboard, move |evt: KbEvent, _| match evt {
-        KbEvent::Enter { surface, .. } => {
+        KbEvent::Enter { surface, serial, .. } => {
+            *my_serial.lock().unwrap() = serial;
             let wid = make_wid(&surface);
             my_sink
                 .lock()
@@ -33,6 +37,7 @@ pub fn init_keyboard(
             target = None;
         }
         KbEvent::Key {
+            serial,
             modifiers,
             rawkey,
             keysym,
@@ -40,6 +45,7 @@ pub fn init_keyboard(
             utf8,
             ..
         } => {
+            *my_serial.lock().unwrap() = serial;
             if let Some(wid) = target {
                 let state = match state {
                     wl_keyboard::KeyState::Pressed => ElementState::Pressed,
@@ -86,9 +92,11 @@ pub fn init_keyboard(
             // { variables to be captured by the closure
             let mut target = None;
             let my_sink = sink;
+            let my_serial = serial;
             // }
             keyboard.implement(move |evt, _| match evt {
-                wl_keyboard::Event::Enter { surface, .. } => {
+                wl_keyboard::Event::Enter { surface, serial, .. } => {
+                    *my_serial.lock().unwrap() = serial;
                     let wid = make_wid(&surface);
                     my_sink
                         .lock()
@@ -104,7 +112,8 @@ pub fn init_keyboard(
                         .send_event(WindowEvent::Focused(false), wid);
                     target = None;
                 }
-                wl_keyboard::Event::Key { key, state, .. } => {
+                wl_keyboard::Event::Key { serial, key, state, .. } => {
+                    *my_serial.lock().unwrap() = serial;
                     if let Some(wid) = target {
                         let state = match state {
                             wl_keyboard::KeyState::Pressed => ElementState::Pressed,
@@ -130,6 +139,8 @@ pub fn init_keyboard(
     }
 }
 
+// The number row is matched on the raw evdev code, so that it is reported as `Key1`..`Key0` even
+// on layouts (like AZERTY) where those keys produce symbols without a modifier.
 fn key_to_vkey(rawkey: u32, keysym: u32) -> Option<VirtualKeyCode> {
     match rawkey {
         1 => Some(VirtualKeyCode::Escape),
@@ -147,159 +158,6 @@ fn key_to_vkey(rawkey: u32, keysym: u32) -> Option<VirtualKeyCode> {
     }
 }
 
-fn keysym_to_vkey(keysym: u32) -> Option<VirtualKeyCode> {
-    use sctk::keyboard::keysyms;
-    match keysym {
-        // letters
-        keysyms::XKB_KEY_A | keysyms::XKB_KEY_a => Some(VirtualKeyCode::A),
-        keysyms::XKB_KEY_B | keysyms::XKB_KEY_b => Some(VirtualKeyCode::B),
-        keysyms::XKB_KEY_C | keysyms::XKB_KEY_c => Some(VirtualKeyCode::C),
-        keysyms::XKB_KEY_D | keysyms::XKB_KEY_d => Some(VirtualKeyCode::D),
-        keysyms::XKB_KEY_E | keysyms::XKB_KEY_e => Some(VirtualKeyCode::E),
-        keysyms::XKB_KEY_F | keysyms::XKB_KEY_f => Some(VirtualKeyCode::F),
-        keysyms::XKB_KEY_G | keysyms::XKB_KEY_g => Some(VirtualKeyCode::G),
-        keysyms::XKB_KEY_H | keysyms::XKB_KEY_h => Some(VirtualKeyCode::H),
-        keysyms::XKB_KEY_I | keysyms::XKB_KEY_i => Some(VirtualKeyCode::I),
-        keysyms::XKB_KEY_J | keysyms::XKB_KEY_j => Some(VirtualKeyCode::J),
-        keysyms::XKB_KEY_K | keysyms::XKB_KEY_k => Some(VirtualKeyCode::K),
-        keysyms::XKB_KEY_L | keysyms::XKB_KEY_l => Some(VirtualKeyCode::L),
-        keysyms::XKB_KEY_M | keysyms::XKB_KEY_m => Some(VirtualKeyCode::M),
-        keysyms::XKB_KEY_N | keysyms::XKB_KEY_n => Some(VirtualKeyCode::N),
-        keysyms::XKB_KEY_O | keysyms::XKB_KEY_o => Some(VirtualKeyCode::O),
-        keysyms::XKB_KEY_P | keysyms::XKB_KEY_p => Some(VirtualKeyCode::P),
-        keysyms::XKB_KEY_Q | keysyms::XKB_KEY_q => Some(VirtualKeyCode::Q),
-        keysyms::XKB_KEY_R | keysyms::XKB_KEY_r => Some(VirtualKeyCode::R),
-        keysyms::XKB_KEY_S | keysyms::XKB_KEY_s => Some(VirtualKeyCode::S),
-        keysyms::XKB_KEY_T | keysyms::XKB_KEY_t => Some(VirtualKeyCode::T),
-        keysyms::XKB_KEY_U | keysyms::XKB_KEY_u => Some(VirtualKeyCode::U),
-        keysyms::XKB_KEY_V | keysyms::XKB_KEY_v => Some(VirtualKeyCode::V),
-        keysyms::XKB_KEY_W | keysyms::XKB_KEY_w => Some(VirtualKeyCode::W),
-        keysyms::XKB_KEY_X | keysyms::XKB_KEY_x => Some(VirtualKeyCode::X),
-        keysyms::XKB_KEY_Y | keysyms::XKB_KEY_y => Some(VirtualKeyCode::Y),
-        keysyms::XKB_KEY_Z | keysyms::XKB_KEY_z => Some(VirtualKeyCode::Z),
-        // F--
-        keysyms::XKB_KEY_F1 => Some(VirtualKeyCode::F1),
-        keysyms::XKB_KEY_F2 => Some(VirtualKeyCode::F2),
-        keysyms::XKB_KEY_F3 => Some(VirtualKeyCode::F3),
-        keysyms::XKB_KEY_F4 => Some(VirtualKeyCode::F4),
-        keysyms::XKB_KEY_F5 => Some(VirtualKeyCode::F5),
-        keysyms::XKB_KEY_F6 => Some(VirtualKeyCode::F6),
-        keysyms::XKB_KEY_F7 => Some(VirtualKeyCode::F7),
-        keysyms::XKB_KEY_F8 => Some(VirtualKeyCode::F8),
-        keysyms::XKB_KEY_F9 => Some(VirtualKeyCode::F9),
-        keysyms::XKB_KEY_F10 => Some(VirtualKeyCode::F10),
-        keysyms::XKB_KEY_F11 => Some(VirtualKeyCode::F11),
-        keysyms::XKB_KEY_F12 => Some(VirtualKeyCode::F12),
-        keysyms::XKB_KEY_F13 => Some(VirtualKeyCode::F13),
-        keysyms::XKB_KEY_F14 => Some(VirtualKeyCode::F14),
-        keysyms::XKB_KEY_F15 => Some(VirtualKeyCode::F15),
-        // flow control
-        keysyms::XKB_KEY_Print => Some(VirtualKeyCode::Snapshot),
-        keysyms::XKB_KEY_Scroll_Lock => Some(VirtualKeyCode::Scroll),
-        keysyms::XKB_KEY_Pause => Some(VirtualKeyCode::Pause),
-        keysyms::XKB_KEY_Insert => Some(VirtualKeyCode::Insert),
-        keysyms::XKB_KEY_Home => Some(VirtualKeyCode::Home),
-        keysyms::XKB_KEY_Delete => Some(VirtualKeyCode::Delete),
-        keysyms::XKB_KEY_End => Some(VirtualKeyCode::End),
-        keysyms::XKB_KEY_Page_Down => Some(VirtualKeyCode::PageDown),
-        keysyms::XKB_KEY_Page_Up => Some(VirtualKeyCode::PageUp),
-        // arrows
-        keysyms::XKB_KEY_Left => Some(VirtualKeyCode::Left),
-        keysyms::XKB_KEY_Up => Some(VirtualKeyCode::Up),
-        keysyms::XKB_KEY_Right => Some(VirtualKeyCode::Right),
-        keysyms::XKB_KEY_Down => Some(VirtualKeyCode::Down),
-        //
-        keysyms::XKB_KEY_BackSpace => Some(VirtualKeyCode::Back),
-        keysyms::XKB_KEY_Return => Some(VirtualKeyCode::Return),
-        keysyms::XKB_KEY_space => Some(VirtualKeyCode::Space),
-        // keypad
-        keysyms::XKB_KEY_Num_Lock => Some(VirtualKeyCode::Numlock),
-        keysyms::XKB_KEY_KP_0 => Some(VirtualKeyCode::Numpad0),
-        keysyms::XKB_KEY_KP_1 => Some(VirtualKeyCode::Numpad1),
-        keysyms::XKB_KEY_KP_2 => Some(VirtualKeyCode::Numpad2),
-        keysyms::XKB_KEY_KP_3 => Some(VirtualKeyCode::Numpad3),
-        keysyms::XKB_KEY_KP_4 => Some(VirtualKeyCode::Numpad4),
-        keysyms::XKB_KEY_KP_5 => Some(VirtualKeyCode::Numpad5),
-        keysyms::XKB_KEY_KP_6 => Some(VirtualKeyCode::Numpad6),
-        keysyms::XKB_KEY_KP_7 => Some(VirtualKeyCode::Numpad7),
-        keysyms::XKB_KEY_KP_8 => Some(VirtualKeyCode::Numpad8),
-        keysyms::XKB_KEY_KP_9 => Some(VirtualKeyCode::Numpad9),
-        // misc
-        // => Some(VirtualKeyCode::AbntC1),
-        // => Some(VirtualKeyCode::AbntC2),
-        keysyms::XKB_KEY_plus => Some(VirtualKeyCode::Add),
-        keysyms::XKB_KEY_apostrophe => Some(VirtualKeyCode::Apostrophe),
-        // => Some(VirtualKeyCode::Apps),
-        // => Some(VirtualKeyCode::At),
-        // => Some(VirtualKeyCode::Ax),
-        keysyms::XKB_KEY_backslash => Some(VirtualKeyCode::Backslash),
-        // => Some(VirtualKeyCode::Calculator),
-        // => Some(VirtualKeyCode::Capital),
-        keysyms::XKB_KEY_colon => Some(VirtualKeyCode::Colon),
-        keysyms::XKB_KEY_comma => Some(VirtualKeyCode::Comma),
-        // => Some(VirtualKeyCode::Convert),
-        // => Some(VirtualKeyCode::Decimal),
-        // => Some(VirtualKeyCode::Divide),
-        keysyms::XKB_KEY_equal => Some(VirtualKeyCode::Equals),
-        // => Some(VirtualKeyCode::Grave),
-        // => Some(VirtualKeyCode::Kana),
-        // => Some(VirtualKeyCode::Kanji),
-        keysyms::XKB_KEY_Alt_L => Some(VirtualKeyCode::LAlt),
-        // => Some(VirtualKeyCode::LBracket),
-        keysyms::XKB_KEY_Control_L => Some(VirtualKeyCode::LControl),
-        keysyms::XKB_KEY_Shift_L => Some(VirtualKeyCode::LShift),
-        // => Some(VirtualKeyCode::LWin),
-        // => Some(VirtualKeyCode::Mail),
-        // => Some(VirtualKeyCode::MediaSelect),
-        // => Some(VirtualKeyCode::MediaStop),
-        keysyms::XKB_KEY_minus => Some(VirtualKeyCode::Minus),
-        keysyms::XKB_KEY_asterisk => Some(VirtualKeyCode::Multiply),
-        // => Some(VirtualKeyCode::Mute),
-        // => Some(VirtualKeyCode::MyComputer),
-        // => Some(VirtualKeyCode::NextTrack),
-        // => Some(VirtualKeyCode::NoConvert),
-        keysyms::XKB_KEY_KP_Separator => Some(VirtualKeyCode::NumpadComma),
-        keysyms::XKB_KEY_KP_Enter => Some(VirtualKeyCode::NumpadEnter),
-        keysyms::XKB_KEY_KP_Equal => Some(VirtualKeyCode::NumpadEquals),
-        // => Some(VirtualKeyCode::OEM102),
-        // => Some(VirtualKeyCode::Period),
-        // => Some(VirtualKeyCode::Playpause),
-        // => Some(VirtualKeyCode::Power),
-        // => Some(VirtualKeyCode::Prevtrack),
-        keysyms::XKB_KEY_Alt_R => Some(VirtualKeyCode::RAlt),
-        // => Some(VirtualKeyCode::RBracket),
-        keysyms::XKB_KEY_Control_R => Some(VirtualKeyCode::RControl),
-        keysyms::XKB_KEY_Shift_R => Some(VirtualKeyCode::RShift),
-        // => Some(VirtualKeyCode::RWin),
-        keysyms::XKB_KEY_semicolon => Some(VirtualKeyCode::Semicolon),
-        keysyms::XKB_KEY_slash => Some(VirtualKeyCode::Slash),
-        // => Some(VirtualKeyCode::Sleep),
-        // => Some(VirtualKeyCode::Stop),
-        // => Some(VirtualKeyCode::Subtract),
-        // => Some(VirtualKeyCode::Sysrq),
-        keysyms::XKB_KEY_Tab => Some(VirtualKeyCode::Tab),
-        keysyms::XKB_KEY_ISO_Left_Tab => Some(VirtualKeyCode::Tab),
-        // => Some(VirtualKeyCode::Underline),
-        // => Some(VirtualKeyCode::Unlabeled),
-        keysyms::XKB_KEY_XF86AudioLowerVolume => Some(VirtualKeyCode::VolumeDown),
-        keysyms::XKB_KEY_XF86AudioRaiseVolume => Some(VirtualKeyCode::VolumeUp),
-        // => Some(VirtualKeyCode::Wake),
-        // => Some(VirtualKeyCode::Webback),
-        // => Some(VirtualKeyCode::WebFavorites),
-        // => Some(VirtualKeyCode::WebForward),
-        // => Some(VirtualKeyCode::WebHome),
-        // => Some(VirtualKeyCode::WebRefresh),
-        // => Some(VirtualKeyCode::WebSearch),
-        // => Some(VirtualKeyCode::WebStop),
-        // => Some(VirtualKeyCode::Yen),
-        keysyms::XKB_KEY_XF86Copy => Some(VirtualKeyCode::Copy),
-        keysyms::XKB_KEY_XF86Paste => Some(VirtualKeyCode::Paste),
-        keysyms::XKB_KEY_XF86Cut => Some(VirtualKeyCode::Cut),
-        // fallback
-        _ => None,
-    }
-}
-
 impl From<keyboard::ModifiersState> for ModifiersState {
     fn from(mods: keyboard::ModifiersState) -> ModifiersState {
         ModifiersState {
@@ -310,3 +168,202 @@ impl From<keyboard::ModifiersState> for ModifiersState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use VirtualKeyCode;
+    use super::{key_to_vkey, keysym_to_vkey};
+
+    // evdev's `KEY_RESERVED`, which never takes the number row override.
+    const NO_RAWKEY: u32 = 0;
+    // evdev's `KEY_MINUS` and `KEY_BACK`, which are matched by their keysym.
+    const KEY_MINUS: u32 = 12;
+    const KEY_BACK: u32 = 158;
+
+    // The table the X11 backend had before it was shared, which the shared one must keep.
+    fn old_x11_table() -> Vec<(u32, VirtualKeyCode)> {
+        use x11_dl::keysym::*;
+        vec![
+            (XK_BackSpace, VirtualKeyCode::Back),
+            (XK_Tab, VirtualKeyCode::Tab),
+            (XK_Return, VirtualKeyCode::Return),
+            (XK_Escape, VirtualKeyCode::Escape),
+            (XK_Delete, VirtualKeyCode::Delete),
+            (XK_Multi_key, VirtualKeyCode::Compose),
+            (XK_Home, VirtualKeyCode::Home),
+            (XK_Left, VirtualKeyCode::Left),
+            (XK_Up, VirtualKeyCode::Up),
+            (XK_Right, VirtualKeyCode::Right),
+            (XK_Down, VirtualKeyCode::Down),
+            (XK_Page_Up, VirtualKeyCode::PageUp),
+            (XK_Page_Down, VirtualKeyCode::PageDown),
+            (XK_End, VirtualKeyCode::End),
+            (XK_Insert, VirtualKeyCode::Insert),
+            (XK_KP_Home, VirtualKeyCode::Home),
+            (XK_KP_Left, VirtualKeyCode::Left),
+            (XK_KP_Up, VirtualKeyCode::Up),
+            (XK_KP_Right, VirtualKeyCode::Right),
+            (XK_KP_Down, VirtualKeyCode::Down),
+            (XK_KP_Page_Up, VirtualKeyCode::PageUp),
+            (XK_KP_Page_Down, VirtualKeyCode::PageDown),
+            (XK_KP_End, VirtualKeyCode::End),
+            (XK_KP_Insert, VirtualKeyCode::Insert),
+            (XK_KP_Delete, VirtualKeyCode::Delete),
+            (XK_KP_Equal, VirtualKeyCode::NumpadEquals),
+            (XK_KP_0, VirtualKeyCode::Numpad0),
+            (XK_KP_1, VirtualKeyCode::Numpad1),
+            (XK_KP_2, VirtualKeyCode::Numpad2),
+            (XK_KP_3, VirtualKeyCode::Numpad3),
+            (XK_KP_4, VirtualKeyCode::Numpad4),
+            (XK_KP_5, VirtualKeyCode::Numpad5),
+            (XK_KP_6, VirtualKeyCode::Numpad6),
+            (XK_KP_7, VirtualKeyCode::Numpad7),
+            (XK_KP_8, VirtualKeyCode::Numpad8),
+            (XK_KP_9, VirtualKeyCode::Numpad9),
+            (XK_F1, VirtualKeyCode::F1),
+            (XK_F2, VirtualKeyCode::F2),
+            (XK_F3, VirtualKeyCode::F3),
+            (XK_F4, VirtualKeyCode::F4),
+            (XK_F5, VirtualKeyCode::F5),
+            (XK_F6, VirtualKeyCode::F6),
+            (XK_F7, VirtualKeyCode::F7),
+            (XK_F8, VirtualKeyCode::F8),
+            (XK_F9, VirtualKeyCode::F9),
+            (XK_F10, VirtualKeyCode::F10),
+            (XK_F11, VirtualKeyCode::F11),
+            (XK_F12, VirtualKeyCode::F12),
+            (XK_F13, VirtualKeyCode::F13),
+            (XK_F14, VirtualKeyCode::F14),
+            (XK_F15, VirtualKeyCode::F15),
+            (XK_Shift_L, VirtualKeyCode::LShift),
+            (XK_Shift_R, VirtualKeyCode::RShift),
+            (XK_Control_L, VirtualKeyCode::LControl),
+            (XK_Control_R, VirtualKeyCode::RControl),
+            (XK_Alt_L, VirtualKeyCode::LAlt),
+            (XK_Alt_R, VirtualKeyCode::RAlt),
+            (XK_ISO_Left_Tab, VirtualKeyCode::Tab),
+            (XK_space, VirtualKeyCode::Space),
+            (XK_apostrophe, VirtualKeyCode::Apostrophe),
+            (XK_plus, VirtualKeyCode::Add),
+            (XK_comma, VirtualKeyCode::Comma),
+            // Was `Subtract`, which belongs to the keypad's minus.
+            (XK_minus, VirtualKeyCode::Minus),
+            (XK_period, VirtualKeyCode::Period),
+            (XK_slash, VirtualKeyCode::Slash),
+            (XK_0, VirtualKeyCode::Key0),
+            (XK_1, VirtualKeyCode::Key1),
+            (XK_2, VirtualKeyCode::Key2),
+            (XK_3, VirtualKeyCode::Key3),
+            (XK_4, VirtualKeyCode::Key4),
+            (XK_5, VirtualKeyCode::Key5),
+            (XK_6, VirtualKeyCode::Key6),
+            (XK_7, VirtualKeyCode::Key7),
+            (XK_8, VirtualKeyCode::Key8),
+            (XK_9, VirtualKeyCode::Key9),
+            (XK_colon, VirtualKeyCode::Colon),
+            (XK_semicolon, VirtualKeyCode::Semicolon),
+            (XK_equal, VirtualKeyCode::Equals),
+            (XK_at, VirtualKeyCode::At),
+            (XK_A, VirtualKeyCode::A),
+            (XK_B, VirtualKeyCode::B),
+            (XK_C, VirtualKeyCode::C),
+            (XK_D, VirtualKeyCode::D),
+            (XK_E, VirtualKeyCode::E),
+            (XK_F, VirtualKeyCode::F),
+            (XK_G, VirtualKeyCode::G),
+            (XK_H, VirtualKeyCode::H),
+            (XK_I, VirtualKeyCode::I),
+            (XK_J, VirtualKeyCode::J),
+            (XK_K, VirtualKeyCode::K),
+            (XK_L, VirtualKeyCode::L),
+            (XK_M, VirtualKeyCode::M),
+            (XK_N, VirtualKeyCode::N),
+            (XK_O, VirtualKeyCode::O),
+            (XK_P, VirtualKeyCode::P),
+            (XK_Q, VirtualKeyCode::Q),
+            (XK_R, VirtualKeyCode::R),
+            (XK_S, VirtualKeyCode::S),
+            (XK_T, VirtualKeyCode::T),
+            (XK_U, VirtualKeyCode::U),
+            (XK_V, VirtualKeyCode::V),
+            (XK_W, VirtualKeyCode::W),
+            (XK_X, VirtualKeyCode::X),
+            (XK_Y, VirtualKeyCode::Y),
+            (XK_Z, VirtualKeyCode::Z),
+            (XK_bracketleft, VirtualKeyCode::LBracket),
+            (XK_backslash, VirtualKeyCode::Backslash),
+            (XK_bracketright, VirtualKeyCode::RBracket),
+            (XK_grave, VirtualKeyCode::Grave),
+            (XK_a, VirtualKeyCode::A),
+            (XK_b, VirtualKeyCode::B),
+            (XK_c, VirtualKeyCode::C),
+            (XK_d, VirtualKeyCode::D),
+            (XK_e, VirtualKeyCode::E),
+            (XK_f, VirtualKeyCode::F),
+            (XK_g, VirtualKeyCode::G),
+            (XK_h, VirtualKeyCode::H),
+            (XK_i, VirtualKeyCode::I),
+            (XK_j, VirtualKeyCode::J),
+            (XK_k, VirtualKeyCode::K),
+            (XK_l, VirtualKeyCode::L),
+            (XK_m, VirtualKeyCode::M),
+            (XK_n, VirtualKeyCode::N),
+            (XK_o, VirtualKeyCode::O),
+            (XK_p, VirtualKeyCode::P),
+            (XK_q, VirtualKeyCode::Q),
+            (XK_r, VirtualKeyCode::R),
+            (XK_s, VirtualKeyCode::S),
+            (XK_t, VirtualKeyCode::T),
+            (XK_u, VirtualKeyCode::U),
+            (XK_v, VirtualKeyCode::V),
+            (XK_w, VirtualKeyCode::W),
+            (XK_x, VirtualKeyCode::X),
+            (XK_y, VirtualKeyCode::Y),
+            (XK_z, VirtualKeyCode::Z),
+            (XF86XK_Back, VirtualKeyCode::NavigateBackward),
+            (XF86XK_Forward, VirtualKeyCode::NavigateForward),
+            (XF86XK_Copy, VirtualKeyCode::Copy),
+            (XF86XK_Paste, VirtualKeyCode::Paste),
+            (XF86XK_Cut, VirtualKeyCode::Cut),
+        ]
+    }
+
+    #[test]
+    fn agrees_with_x11() {
+        // X11 feeds keysyms straight into the shared table, while Wayland goes through
+        // `key_to_vkey` for the raw key overrides.
+        for (keysym, vkey) in old_x11_table() {
+            assert_eq!(keysym_to_vkey(keysym), Some(vkey), "X11 changed keysym {:#x}", keysym);
+            assert_eq!(
+                key_to_vkey(NO_RAWKEY, keysym),
+                Some(vkey),
+                "Wayland and X11 disagree on keysym {:#x}",
+                keysym,
+            );
+        }
+    }
+
+    #[test]
+    fn minus_is_not_subtract() {
+        use x11_dl::keysym::{XK_KP_Subtract, XK_minus};
+        assert_eq!(keysym_to_vkey(XK_minus), Some(VirtualKeyCode::Minus));
+        assert_eq!(keysym_to_vkey(XK_KP_Subtract), Some(VirtualKeyCode::Subtract));
+        assert_eq!(key_to_vkey(KEY_MINUS, XK_minus), Some(VirtualKeyCode::Minus));
+    }
+
+    #[test]
+    fn navigation_keys() {
+        use x11_dl::keysym::{XF86XK_Back, XF86XK_Forward};
+        assert_eq!(keysym_to_vkey(XF86XK_Back), Some(VirtualKeyCode::NavigateBackward));
+        assert_eq!(keysym_to_vkey(XF86XK_Forward), Some(VirtualKeyCode::NavigateForward));
+        assert_eq!(key_to_vkey(KEY_BACK, XF86XK_Back), Some(VirtualKeyCode::NavigateBackward));
+    }
+
+    #[test]
+    fn number_row_uses_rawkey() {
+        use x11_dl::keysym::{XK_ampersand, XK_agrave};
+        // AZERTY: the `1` key produces `&` and the `0` key produces `à`.
+        assert_eq!(key_to_vkey(2, XK_ampersand), Some(VirtualKeyCode::Key1));
+        assert_eq!(key_to_vkey(11, XK_agrave), Some(VirtualKeyCode::Key0));
+    }
+}
@@ -7,10 +7,18 @@ pub use self::event_loop::{EventsLoop, EventsLoopProxy, EventsLoopSink, MonitorI
 use sctk::reexports::client::protocol::wl_surface;
 use sctk::reexports::client::Proxy;
 
+mod cursor;
+mod data_device;
 mod event_loop;
 mod pointer;
+mod pointer_constraints;
+mod pointer_gestures;
 mod touch;
 mod keyboard;
+mod primary_selection;
+mod selection;
+mod tasks;
+mod text_input;
 mod window;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -3,36 +3,119 @@ use std::sync::{Arc, Mutex};
 use {ElementState, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent};
 use events::ModifiersState;
 
-use super::DeviceId;
+use super::{DeviceId, WindowId};
 use super::event_loop::EventsLoopSink;
 use super::window::WindowStore;
 
 use sctk::reexports::client::{NewProxy, Proxy};
 use sctk::reexports::client::protocol::wl_pointer::{self, Event as PtrEvent, WlPointer};
 
+// Where the pointer of a seat is and which of its buttons are held.
+#[derive(Default)]
+pub struct SeatPointer {
+    pub focus: Option<(WindowId, (f64, f64))>,
+    pub buttons: Vec<MouseButton>,
+    // The window and serial of the press that started the implicit grab of the held buttons.
+    pub press: Option<(WindowId, u32)>,
+}
+
+// The pointers of all seats, keyed by the seat's global id, the last one used first.
+#[derive(Default)]
+pub struct PointerTracker {
+    seats: Vec<(u32, SeatPointer)>,
+}
+
+impl PointerTracker {
+    // Returns the pointer of the seat, making it the last one used.
+    pub fn seat(&mut self, seat_id: u32) -> &mut SeatPointer {
+        let idx = self.seats.iter().position(|&(id, _)| id == seat_id);
+        let seat = match idx {
+            Some(idx) => self.seats.remove(idx).1,
+            None => SeatPointer::default(),
+        };
+        self.seats.insert(0, (seat_id, seat));
+        &mut self.seats[0].1
+    }
+
+    pub fn remove_seat(&mut self, seat_id: u32) {
+        self.seats.retain(|&(id, _)| id != seat_id);
+    }
+
+    // The window the last used pointer is over, and where.
+    pub fn focus(&self) -> Option<(WindowId, (f64, f64))> {
+        self.seats.iter().filter_map(|&(_, ref seat)| seat.focus).next()
+    }
+
+    // Where the last used pointer over the window is.
+    pub fn position_in(&self, wid: WindowId) -> Option<(f64, f64)> {
+        self.seats
+            .iter()
+            .filter_map(|&(_, ref seat)| match seat.focus {
+                Some((focus, position)) if focus == wid => Some(position),
+                _ => None,
+            })
+            .next()
+    }
+
+    // The buttons held on any seat.
+    pub fn buttons(&self) -> Vec<MouseButton> {
+        let mut buttons = Vec::new();
+        for &(_, ref seat) in &self.seats {
+            for &button in &seat.buttons {
+                if !buttons.contains(&button) {
+                    buttons.push(button);
+                }
+            }
+        }
+        buttons
+    }
+
+    // The serial and seat of the last press still held over the window.
+    pub fn press_on(&self, wid: WindowId) -> Option<(u32, u32)> {
+        self.seats
+            .iter()
+            .filter_map(|&(seat_id, ref seat)| match seat.press {
+                Some((press_wid, serial)) if press_wid == wid => Some((serial, seat_id)),
+                _ => None,
+            })
+            .next()
+    }
+}
+
 pub fn implement_pointer(
     pointer: NewProxy<WlPointer>,
     sink: Arc<Mutex<EventsLoopSink>>,
     store: Arc<Mutex<WindowStore>>,
+    serial: Arc<Mutex<u32>>,
+    tracker: Arc<Mutex<PointerTracker>>,
+    seat_id: u32,
 ) -> Proxy<WlPointer> {
     let mut mouse_focus = None;
     let mut axis_buffer = None;
     let mut axis_discrete_buffer = None;
-    let mut axis_state = TouchPhase::Ended;
+    let mut axis_source = None;
+    let mut axis_stopped = false;
+    // Whether fingers are scrolling, between the first motion and the `axis_stop` event.
+    let mut finger_scrolling = false;
 
     pointer.implement(move |evt, pointer: Proxy<_>| {
         let mut sink = sink.lock().unwrap();
         let store = store.lock().unwrap();
         match evt {
             PtrEvent::Enter {
+                serial: enter_serial,
                 surface,
                 surface_x,
                 surface_y,
-                ..
             } => {
+                *serial.lock().unwrap() = enter_serial;
+                if let Some(cursor) = store.find_cursor(&surface) {
+                    cursor.lock().unwrap().pointer_entered(&pointer, enter_serial);
+                }
                 let wid = store.find_wid(&surface);
                 if let Some(wid) = wid {
                     mouse_focus = Some(wid);
+                    tracker.lock().unwrap().seat(seat_id).focus = Some((wid, (surface_x, surface_y)));
                     sink.send_event(
                         WindowEvent::CursorEntered {
                             device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
@@ -52,8 +135,32 @@ pub fn implement_pointer(
             }
             PtrEvent::Leave { surface, .. } => {
                 mouse_focus = None;
+                {
+                    // Buttons released elsewhere aren't reported to the window.
+                    let mut tracker = tracker.lock().unwrap();
+                    let seat = tracker.seat(seat_id);
+                    seat.focus = None;
+                    seat.buttons.clear();
+                    seat.press = None;
+                }
+                if let Some(cursor) = store.find_cursor(&surface) {
+                    cursor.lock().unwrap().pointer_left(&pointer);
+                }
                 let wid = store.find_wid(&surface);
                 if let Some(wid) = wid {
+                    if finger_scrolling {
+                        finger_scrolling = false;
+                        sink.send_event(
+                            WindowEvent::MouseWheel {
+                                device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
+                                delta: MouseScrollDelta::PixelDelta((0.0, 0.0).into()),
+                                phase: TouchPhase::Ended,
+                                // TODO: replace dummy value with actual modifier state
+                                modifiers: ModifiersState::default(),
+                            },
+                            wid,
+                        );
+                    }
                     sink.send_event(
                         WindowEvent::CursorLeft {
                             device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
@@ -68,6 +175,7 @@ pub fn implement_pointer(
                 ..
             } => {
                 if let Some(wid) = mouse_focus {
+                    tracker.lock().unwrap().seat(seat_id).focus = Some((wid, (surface_x, surface_y)));
                     sink.send_event(
                         WindowEvent::CursorMoved {
                             device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
@@ -79,7 +187,8 @@ pub fn implement_pointer(
                     );
                 }
             }
-            PtrEvent::Button { button, state, .. } => {
+            PtrEvent::Button { serial: button_serial, button, state, .. } => {
+                *serial.lock().unwrap() = button_serial;
                 if let Some(wid) = mouse_focus {
                     let state = match state {
                         wl_pointer::ButtonState::Pressed => ElementState::Pressed,
@@ -89,9 +198,28 @@ pub fn implement_pointer(
                         0x110 => MouseButton::Left,
                         0x111 => MouseButton::Right,
                         0x112 => MouseButton::Middle,
-                        // TODO figure out the translation ?
+                        // BTN_SIDE and BTN_EXTRA
+                        0x113 => MouseButton::Back,
+                        0x114 => MouseButton::Forward,
+                        // The buttons after them are numbered like on X11, where BTN_SIDE is 8.
+                        button if button > 0x114 && button - 0x113 + 8 <= 0xff => {
+                            MouseButton::Other((button - 0x113 + 8) as u8)
+                        }
                         _ => return,
                     };
+                    {
+                        let mut tracker = tracker.lock().unwrap();
+                        let seat = tracker.seat(seat_id);
+                        seat.buttons.retain(|&b| b != button);
+                        if state == ElementState::Pressed {
+                            if seat.buttons.is_empty() {
+                                seat.press = Some((wid, button_serial));
+                            }
+                            seat.buttons.push(button);
+                        } else if seat.buttons.is_empty() {
+                            seat.press = None;
+                        }
+                    }
                     sink.send_event(
                         WindowEvent::MouseInput {
                             device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
@@ -132,34 +260,56 @@ pub fn implement_pointer(
                             wl_pointer::Axis::HorizontalScroll => x += value as f32,
                         }
                         axis_buffer = Some((x, y));
-                        axis_state = match axis_state {
-                            TouchPhase::Started | TouchPhase::Moved => TouchPhase::Moved,
-                            _ => TouchPhase::Started,
-                        }
                     }
                 }
             }
             PtrEvent::Frame => {
                 let axis_buffer = axis_buffer.take();
                 let axis_discrete_buffer = axis_discrete_buffer.take();
+                let axis_source = axis_source.take();
+                let stopped = axis_stopped;
+                axis_stopped = false;
                 if let Some(wid) = mouse_focus {
                     if let Some((x, y)) = axis_discrete_buffer {
+                        // A wheel turns by steps, which don't make up a sequence.
                         sink.send_event(
                             WindowEvent::MouseWheel {
                                 device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
                                 delta: MouseScrollDelta::LineDelta(x as f32, y as f32),
-                                phase: axis_state,
+                                phase: TouchPhase::Moved,
                                 // TODO: replace dummy value with actual modifier state
                                 modifiers: ModifiersState::default(),
                             },
                             wid,
                         );
                     } else if let Some((x, y)) = axis_buffer {
+                        // Only scrolling with fingers is ended by `axis_stop`, so other continuous
+                        // sources are reported as moving.
+                        let phase = match axis_source {
+                            Some(wl_pointer::AxisSource::Finger) if !finger_scrolling => {
+                                finger_scrolling = true;
+                                TouchPhase::Started
+                            }
+                            _ => TouchPhase::Moved,
+                        };
                         sink.send_event(
                             WindowEvent::MouseWheel {
                                 device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
                                 delta: MouseScrollDelta::PixelDelta((x as f64, y as f64).into()),
-                                phase: axis_state,
+                                phase: phase,
+                                // TODO: replace dummy value with actual modifier state
+                                modifiers: ModifiersState::default(),
+                            },
+                            wid,
+                        );
+                    }
+                    if stopped && finger_scrolling {
+                        finger_scrolling = false;
+                        sink.send_event(
+                            WindowEvent::MouseWheel {
+                                device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
+                                delta: MouseScrollDelta::PixelDelta((0.0, 0.0).into()),
+                                phase: TouchPhase::Ended,
                                 // TODO: replace dummy value with actual modifier state
                                 modifiers: ModifiersState::default(),
                             },
@@ -168,9 +318,11 @@ pub fn implement_pointer(
                     }
                 }
             }
-            PtrEvent::AxisSource { .. } => (),
+            PtrEvent::AxisSource { axis_source: source } => {
+                axis_source = Some(source);
+            }
             PtrEvent::AxisStop { .. } => {
-                axis_state = TouchPhase::Ended;
+                axis_stopped = true;
             }
             PtrEvent::AxisDiscrete { axis, discrete } => {
                 let (mut x, mut y) = axis_discrete_buffer.unwrap_or((0, 0));
@@ -180,10 +332,6 @@ pub fn implement_pointer(
                     wl_pointer::Axis::HorizontalScroll => x += discrete,
                 }
                 axis_discrete_buffer = Some((x, y));
-                axis_state = match axis_state {
-                    TouchPhase::Started | TouchPhase::Moved => TouchPhase::Moved,
-                    _ => TouchPhase::Started,
-                }
             }
         }
     })
@@ -0,0 +1,223 @@
+use std::sync::{Arc, Mutex};
+
+use {CursorGrabMode, DeviceEvent};
+
+use super::DeviceId;
+use super::event_loop::EventsLoopSink;
+
+use sctk::reexports::client::Proxy;
+use sctk::reexports::client::protocol::{wl_compositor, wl_pointer, wl_surface};
+use sctk::reexports::client::protocol::wl_compositor::RequestsTrait as CompositorRequests;
+use sctk::reexports::client::protocol::wl_region::RequestsTrait as RegionRequests;
+use sctk::reexports::protocols::unstable::pointer_constraints::v1::client::{
+    zwp_confined_pointer_v1,
+    zwp_locked_pointer_v1,
+    zwp_pointer_constraints_v1,
+};
+use sctk::reexports::protocols::unstable::relative_pointer::v1::client::{
+    zwp_relative_pointer_manager_v1,
+    zwp_relative_pointer_v1,
+};
+use self::zwp_confined_pointer_v1::{RequestsTrait as ConfinedPointerRequests, ZwpConfinedPointerV1};
+use self::zwp_locked_pointer_v1::{RequestsTrait as LockedPointerRequests, ZwpLockedPointerV1};
+use self::zwp_pointer_constraints_v1::{
+    Lifetime,
+    RequestsTrait as PointerConstraintsRequests,
+    ZwpPointerConstraintsV1,
+};
+use self::zwp_relative_pointer_manager_v1::{
+    RequestsTrait as RelativePointerManagerRequests,
+    ZwpRelativePointerManagerV1,
+};
+use self::zwp_relative_pointer_v1::{
+    Event as RelativePointerEvent,
+    RequestsTrait as RelativePointerRequests,
+    ZwpRelativePointerV1,
+};
+
+// The pointers of all seats, with the globals to constrain them and to get their relative motion.
+pub struct Pointers {
+    sink: Arc<Mutex<EventsLoopSink>>,
+    constraints: Option<Proxy<ZwpPointerConstraintsV1>>,
+    relative_manager: Option<Proxy<ZwpRelativePointerManagerV1>>,
+    pointers: Vec<(Proxy<wl_pointer::WlPointer>, Option<Proxy<ZwpRelativePointerV1>>)>,
+}
+
+impl Pointers {
+    pub fn new(sink: Arc<Mutex<EventsLoopSink>>) -> Pointers {
+        Pointers {
+            sink,
+            constraints: None,
+            relative_manager: None,
+            pointers: Vec::new(),
+        }
+    }
+
+    pub fn set_constraints(&mut self, constraints: Proxy<ZwpPointerConstraintsV1>) {
+        self.constraints = Some(constraints);
+    }
+
+    pub fn set_relative_manager(&mut self, manager: Proxy<ZwpRelativePointerManagerV1>) {
+        for &mut (ref pointer, ref mut relative) in &mut self.pointers {
+            if relative.is_none() {
+                *relative = Some(implement_relative_pointer(&manager, pointer, self.sink.clone()));
+            }
+        }
+        self.relative_manager = Some(manager);
+    }
+
+    pub fn add_pointer(&mut self, pointer: &Proxy<wl_pointer::WlPointer>) {
+        let relative = self.relative_manager
+            .as_ref()
+            .map(|manager| implement_relative_pointer(manager, pointer, self.sink.clone()));
+        self.pointers.push((pointer.clone(), relative));
+    }
+
+    pub fn remove_pointer(&mut self, pointer: &Proxy<wl_pointer::WlPointer>) {
+        if let Some(idx) = self.pointers.iter().position(|&(ref p, _)| p.equals(pointer)) {
+            let (_, relative) = self.pointers.swap_remove(idx);
+            if let Some(relative) = relative {
+                relative.destroy();
+            }
+        }
+    }
+}
+
+// Relative motion is sent whether or not the pointer is constrained, and keeps coming while it's
+// locked in place.
+fn implement_relative_pointer(
+    manager: &Proxy<ZwpRelativePointerManagerV1>,
+    pointer: &Proxy<wl_pointer::WlPointer>,
+    sink: Arc<Mutex<EventsLoopSink>>,
+) -> Proxy<ZwpRelativePointerV1> {
+    manager.get_relative_pointer(pointer).unwrap().implement(move |evt, _| match evt {
+        RelativePointerEvent::RelativeMotion { dx_unaccel, dy_unaccel, .. } => {
+            sink.lock().unwrap().send_raw_event(::Event::DeviceEvent {
+                device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
+                event: DeviceEvent::MouseMotion { delta: (dx_unaccel, dy_unaccel) },
+            });
+        }
+    })
+}
+
+enum Constraint {
+    Confined(Proxy<ZwpConfinedPointerV1>),
+    Locked(Proxy<ZwpLockedPointerV1>),
+}
+
+impl Constraint {
+    fn new(
+        grab: &WindowGrab,
+        constraints: &Proxy<ZwpPointerConstraintsV1>,
+        pointer: &Proxy<wl_pointer::WlPointer>,
+    ) -> Option<Constraint> {
+        let surface = &grab.surface;
+        // A persistent constraint is applied again each time the window gets the pointer focus
+        // back, rather than being gone for good once it's lost.
+        let mode = if grab.area.is_some() { CursorGrabMode::Confined } else { grab.grab };
+        match mode {
+            CursorGrabMode::None => None,
+            CursorGrabMode::Confined => {
+                // The region is copied by the request, so it's not needed afterwards.
+                let region = grab.area.map(|(x, y, width, height)| {
+                    let region = grab.compositor.create_region().unwrap().implement(|_, _| ());
+                    region.add(x, y, width, height);
+                    region
+                });
+                let confined = constraints
+                    .confine_pointer(surface, pointer, region.as_ref(), Lifetime::Persistent)
+                    .unwrap()
+                    .implement(|_, _| ());
+                if let Some(region) = region {
+                    region.destroy();
+                }
+                Some(Constraint::Confined(confined))
+            }
+            CursorGrabMode::Locked => Some(Constraint::Locked(
+                constraints
+                    .lock_pointer(surface, pointer, None, Lifetime::Persistent)
+                    .unwrap()
+                    .implement(|_, _| ()),
+            )),
+        }
+    }
+
+    fn destroy(self) {
+        match self {
+            Constraint::Confined(confined) => confined.destroy(),
+            Constraint::Locked(locked) => locked.destroy(),
+        }
+    }
+}
+
+// The grab of a window, with its constraint on each pointer.
+pub struct WindowGrab {
+    compositor: Proxy<wl_compositor::WlCompositor>,
+    surface: Proxy<wl_surface::WlSurface>,
+    // The mode set with `set_cursor_grab`, which applies while there's no confine area.
+    grab: CursorGrabMode,
+    // The area the pointer is confined to with `confine_cursor_to`, in surface coordinates, if any.
+    area: Option<(i32, i32, i32, i32)>,
+    constraints: Vec<(Proxy<wl_pointer::WlPointer>, Constraint)>,
+}
+
+impl WindowGrab {
+    pub fn new(
+        compositor: &Proxy<wl_compositor::WlCompositor>,
+        surface: &Proxy<wl_surface::WlSurface>,
+    ) -> WindowGrab {
+        WindowGrab {
+            compositor: compositor.clone(),
+            surface: surface.clone(),
+            grab: CursorGrabMode::None,
+            area: None,
+            constraints: Vec::new(),
+        }
+    }
+
+    pub fn set_grab(
+        &mut self,
+        grab: CursorGrabMode,
+        area: Option<(i32, i32, i32, i32)>,
+        pointers: &Pointers,
+    ) -> Result<(), String> {
+        if grab == self.grab && area == self.area {
+            return Ok(());
+        }
+        if (grab != CursorGrabMode::None || area.is_some()) && pointers.constraints.is_none() {
+            return Err("The compositor doesn't support pointer constraints.".to_owned());
+        }
+        for (_, constraint) in self.constraints.drain(..) {
+            constraint.destroy();
+        }
+        self.grab = grab;
+        self.area = area;
+        for &(ref pointer, _) in &pointers.pointers {
+            self.pointer_added(pointer, pointers);
+        }
+        Ok(())
+    }
+
+    // Confines the pointer to `area`, or with `None` goes back to the grab set with `set_grab`.
+    pub fn set_area(&mut self, area: Option<(i32, i32, i32, i32)>, pointers: &Pointers) -> Result<(), String> {
+        let grab = self.grab;
+        self.set_grab(grab, area, pointers)
+    }
+
+    pub fn pointer_added(&mut self, pointer: &Proxy<wl_pointer::WlPointer>, pointers: &Pointers) {
+        let constraint = pointers
+            .constraints
+            .as_ref()
+            .and_then(|constraints| Constraint::new(self, constraints, pointer));
+        if let Some(constraint) = constraint {
+            self.constraints.push((pointer.clone(), constraint));
+        }
+    }
+
+    pub fn pointer_removed(&mut self, pointer: &Proxy<wl_pointer::WlPointer>) {
+        if let Some(idx) = self.constraints.iter().position(|&(ref p, _)| p.equals(pointer)) {
+            let (_, constraint) = self.constraints.swap_remove(idx);
+            constraint.destroy();
+        }
+    }
+}
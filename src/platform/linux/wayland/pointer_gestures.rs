@@ -0,0 +1,174 @@
+use std::sync::{Arc, Mutex};
+
+use {LogicalPosition, TouchPhase, WindowEvent};
+
+use super::{DeviceId, WindowId};
+use super::event_loop::EventsLoopSink;
+use super::window::WindowStore;
+
+use sctk::reexports::client::Proxy;
+use sctk::reexports::client::protocol::wl_pointer;
+use sctk::reexports::protocols::unstable::pointer_gestures::v1::client::{
+    zwp_pointer_gesture_pinch_v1,
+    zwp_pointer_gesture_swipe_v1,
+    zwp_pointer_gestures_v1,
+};
+use self::zwp_pointer_gesture_pinch_v1::{
+    Event as PinchEvent,
+    RequestsTrait as PinchRequests,
+    ZwpPointerGesturePinchV1,
+};
+use self::zwp_pointer_gesture_swipe_v1::{
+    Event as SwipeEvent,
+    RequestsTrait as SwipeRequests,
+    ZwpPointerGestureSwipeV1,
+};
+use self::zwp_pointer_gestures_v1::{RequestsTrait as PointerGesturesRequests, ZwpPointerGesturesV1};
+
+// The gestures of the pointers of all seats, once the compositor gives us the global to get them.
+pub struct PointerGestures {
+    sink: Arc<Mutex<EventsLoopSink>>,
+    store: Arc<Mutex<WindowStore>>,
+    manager: Option<Proxy<ZwpPointerGesturesV1>>,
+    pointers: Vec<(Proxy<wl_pointer::WlPointer>, Option<Gestures>)>,
+}
+
+struct Gestures {
+    swipe: Proxy<ZwpPointerGestureSwipeV1>,
+    pinch: Proxy<ZwpPointerGesturePinchV1>,
+}
+
+impl PointerGestures {
+    pub fn new(sink: Arc<Mutex<EventsLoopSink>>, store: Arc<Mutex<WindowStore>>) -> PointerGestures {
+        PointerGestures {
+            sink,
+            store,
+            manager: None,
+            pointers: Vec::new(),
+        }
+    }
+
+    pub fn set_manager(&mut self, manager: Proxy<ZwpPointerGesturesV1>) {
+        for &mut (ref pointer, ref mut gestures) in &mut self.pointers {
+            if gestures.is_none() {
+                *gestures = Some(implement_gestures(&manager, pointer, &self.sink, &self.store));
+            }
+        }
+        self.manager = Some(manager);
+    }
+
+    pub fn add_pointer(&mut self, pointer: &Proxy<wl_pointer::WlPointer>) {
+        let gestures = self.manager
+            .as_ref()
+            .map(|manager| implement_gestures(manager, pointer, &self.sink, &self.store));
+        self.pointers.push((pointer.clone(), gestures));
+    }
+
+    pub fn remove_pointer(&mut self, pointer: &Proxy<wl_pointer::WlPointer>) {
+        if let Some(idx) = self.pointers.iter().position(|&(ref p, _)| p.equals(pointer)) {
+            let (_, gestures) = self.pointers.swap_remove(idx);
+            if let Some(gestures) = gestures {
+                gestures.swipe.destroy();
+                gestures.pinch.destroy();
+            }
+        }
+    }
+}
+
+fn end_phase(cancelled: i32) -> TouchPhase {
+    if cancelled != 0 {
+        TouchPhase::Cancelled
+    } else {
+        TouchPhase::Ended
+    }
+}
+
+fn implement_gestures(
+    manager: &Proxy<ZwpPointerGesturesV1>,
+    pointer: &Proxy<wl_pointer::WlPointer>,
+    sink: &Arc<Mutex<EventsLoopSink>>,
+    store: &Arc<Mutex<WindowStore>>,
+) -> Gestures {
+    let swipe = {
+        let sink = sink.clone();
+        let store = store.clone();
+        // The window the swipe started on, and how many fingers it's made with
+        let mut focus: Option<(WindowId, u32)> = None;
+        manager.get_swipe_gesture(pointer).unwrap().implement(move |evt, _| {
+            let (wid, fingers, delta, phase) = match evt {
+                SwipeEvent::Begin { surface, fingers, .. } => {
+                    focus = store.lock().unwrap().find_wid(&surface).map(|wid| (wid, fingers));
+                    match focus {
+                        Some((wid, fingers)) => (wid, fingers, (0.0, 0.0), TouchPhase::Started),
+                        None => return,
+                    }
+                }
+                SwipeEvent::Update { dx, dy, .. } => match focus {
+                    Some((wid, fingers)) => (wid, fingers, (dx, dy), TouchPhase::Moved),
+                    None => return,
+                },
+                SwipeEvent::End { cancelled, .. } => match focus.take() {
+                    Some((wid, fingers)) => (wid, fingers, (0.0, 0.0), end_phase(cancelled)),
+                    None => return,
+                },
+            };
+            sink.lock().unwrap().send_event(
+                WindowEvent::SwipeGesture {
+                    device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
+                    fingers,
+                    delta: LogicalPosition::from(delta),
+                    phase,
+                },
+                wid,
+            );
+        })
+    };
+    let pinch = {
+        let sink = sink.clone();
+        let store = store.clone();
+        // The window the pinch started on, and its scale relative to when it started
+        let mut focus: Option<(WindowId, f64)> = None;
+        manager.get_pinch_gesture(pointer).unwrap().implement(move |evt, _| {
+            let (wid, delta, rotation, phase) = match evt {
+                PinchEvent::Begin { surface, .. } => {
+                    focus = store.lock().unwrap().find_wid(&surface).map(|wid| (wid, 1.0));
+                    match focus {
+                        Some((wid, _)) => (wid, 0.0, 0.0, TouchPhase::Started),
+                        None => return,
+                    }
+                }
+                PinchEvent::Update { scale, rotation, .. } => match focus {
+                    Some((wid, ref mut last_scale)) => {
+                        let delta = scale - *last_scale;
+                        *last_scale = scale;
+                        (wid, delta, rotation, TouchPhase::Moved)
+                    }
+                    None => return,
+                },
+                PinchEvent::End { cancelled, .. } => match focus.take() {
+                    Some((wid, _)) => (wid, 0.0, 0.0, end_phase(cancelled)),
+                    None => return,
+                },
+            };
+            let mut sink = sink.lock().unwrap();
+            sink.send_event(
+                WindowEvent::PinchGesture {
+                    device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
+                    delta,
+                    phase,
+                },
+                wid,
+            );
+            sink.send_event(
+                WindowEvent::RotationGesture {
+                    device_id: ::DeviceId(::platform::DeviceId::Wayland(DeviceId)),
+                    // wayland angles are clockwise, which is the opposite of winit
+                    delta: -rotation as f32,
+                    phase,
+                },
+                wid,
+            );
+        })
+    };
+    Gestures { swipe, pinch }
+}
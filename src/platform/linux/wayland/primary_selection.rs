@@ -0,0 +1,105 @@
+use std::os::unix::io::RawFd;
+use std::sync::{Arc, Mutex};
+
+use sctk::reexports::client::{NewProxy, Proxy};
+use sctk::reexports::client::protocol::wl_seat;
+use sctk::reexports::protocols::unstable::primary_selection::v1::client::{
+    zwp_primary_selection_device_manager_v1 as device_manager,
+    zwp_primary_selection_device_v1 as device,
+    zwp_primary_selection_offer_v1 as offer,
+    zwp_primary_selection_source_v1 as source,
+};
+use self::device_manager::RequestsTrait as DeviceManagerRequests;
+use self::device::RequestsTrait as DeviceRequests;
+use self::offer::RequestsTrait as OfferRequests;
+use self::source::RequestsTrait as SourceRequests;
+
+use super::selection::{send_through_pipe, DeviceState, Selection, SelectionProtocol};
+use super::super::clipboard::{self, ClipboardContents};
+
+// The selection protocol of `zwp_primary_selection_device_manager_v1`.
+pub struct PrimarySelectionDevice;
+
+impl SelectionProtocol for PrimarySelectionDevice {
+    type Manager = device_manager::ZwpPrimarySelectionDeviceManagerV1;
+    type Device = device::ZwpPrimarySelectionDeviceV1;
+    type Offer = offer::ZwpPrimarySelectionOfferV1;
+    type Source = source::ZwpPrimarySelectionSourceV1;
+
+    fn get_device(
+        manager: &Proxy<Self::Manager>,
+        seat: &Proxy<wl_seat::WlSeat>,
+    ) -> NewProxy<Self::Device> {
+        manager.get_device(seat).unwrap()
+    }
+
+    fn implement_device(
+        &self,
+        device: NewProxy<Self::Device>,
+        state: Arc<Mutex<DeviceState<Self>>>,
+    ) -> Proxy<Self::Device> {
+        device.implement(move |evt, _| {
+            let mut state = state.lock().unwrap();
+            match evt {
+                device::Event::DataOffer { offer } => state.add_offer(offer),
+                device::Event::Selection { id } => state.set_selection(id),
+            }
+        })
+    }
+
+    fn destroy_device(device: &Proxy<Self::Device>) {
+        device.destroy();
+    }
+
+    fn set_selection(device: &Proxy<Self::Device>, source: &Proxy<Self::Source>, serial: u32) {
+        device.set_selection(Some(source), serial);
+    }
+
+    fn implement_offer(
+        offer: NewProxy<Self::Offer>,
+        mime_types: Arc<Mutex<Vec<String>>>,
+    ) -> Proxy<Self::Offer> {
+        offer.implement(move |evt, _| match evt {
+            offer::Event::Offer { mime_type } => mime_types.lock().unwrap().push(mime_type),
+        })
+    }
+
+    fn receive(offer: &Proxy<Self::Offer>, mime_type: String, fd: RawFd) {
+        offer.receive(mime_type, fd);
+    }
+
+    fn destroy_offer(offer: &Proxy<Self::Offer>) {
+        offer.destroy();
+    }
+
+    fn create_source(manager: &Proxy<Self::Manager>) -> NewProxy<Self::Source> {
+        manager.create_source().unwrap()
+    }
+
+    fn implement_source<F>(
+        source: NewProxy<Self::Source>,
+        contents: Arc<ClipboardContents>,
+        mut cancelled: F,
+    ) -> Proxy<Self::Source>
+    where
+        F: FnMut(&Proxy<Self::Source>) + Send + 'static,
+    {
+        source.implement(move |evt, source: Proxy<source::ZwpPrimarySelectionSourceV1>| match evt {
+            source::Event::Send { mime_type, fd } => {
+                let data = clipboard::find_data(&contents, &[&mime_type]);
+                send_through_pipe(fd, &data.unwrap_or_default());
+            }
+            source::Event::Cancelled => {
+                cancelled(&source);
+                source.destroy();
+            }
+        })
+    }
+
+    fn offer(source: &Proxy<Self::Source>, mime_type: String) {
+        source.offer(mime_type);
+    }
+}
+
+// The primary selection, shared between the event loop and the windows.
+pub type PrimarySelection = Selection<PrimarySelectionDevice>;
@@ -0,0 +1,337 @@
+use std::{io, mem};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::sync::{Arc, Mutex};
+
+use libc;
+
+use sctk::reexports::client::{Display, Interface, NewProxy, Proxy};
+use sctk::reexports::client::protocol::wl_seat;
+
+use super::super::clipboard::{self, ClipboardContents, TEXT_MIME_TYPES};
+
+// How long we wait for the other client to send the next part of the data before giving up.
+pub const RECEIVE_TIMEOUT_MS: libc::c_int = 1000;
+
+// A protocol for sharing a selection between clients, like the data device or the primary
+// selection protocol.
+//
+// They all work the same way through a different set of objects, so `Selection` does the
+// bookkeeping for any of them.
+pub trait SelectionProtocol: Sized + 'static {
+    type Manager: Interface;
+    type Device: Interface;
+    type Offer: Interface;
+    type Source: Interface;
+
+    fn get_device(manager: &Proxy<Self::Manager>, seat: &Proxy<wl_seat::WlSeat>) -> NewProxy<Self::Device>;
+
+    // Handles the events of a new device, which has to keep `state` up to date.
+    fn implement_device(
+        &self,
+        device: NewProxy<Self::Device>,
+        state: Arc<Mutex<DeviceState<Self>>>,
+    ) -> Proxy<Self::Device>;
+
+    fn destroy_device(device: &Proxy<Self::Device>);
+
+    fn set_selection(device: &Proxy<Self::Device>, source: &Proxy<Self::Source>, serial: u32);
+
+    // Adds each mime type the offer is available as to `mime_types`.
+    fn implement_offer(
+        offer: NewProxy<Self::Offer>,
+        mime_types: Arc<Mutex<Vec<String>>>,
+    ) -> Proxy<Self::Offer>;
+
+    fn receive(offer: &Proxy<Self::Offer>, mime_type: String, fd: RawFd);
+
+    fn destroy_offer(offer: &Proxy<Self::Offer>);
+
+    fn create_source(manager: &Proxy<Self::Manager>) -> NewProxy<Self::Source>;
+
+    // Sends `contents` to the clients that ask for them, and calls `cancelled` before destroying
+    // the source once it's been replaced.
+    fn implement_source<F>(
+        source: NewProxy<Self::Source>,
+        contents: Arc<ClipboardContents>,
+        cancelled: F,
+    ) -> Proxy<Self::Source>
+    where
+        F: FnMut(&Proxy<Self::Source>) + Send + 'static;
+
+    fn offer(source: &Proxy<Self::Source>, mime_type: String);
+}
+
+// Data offered by another client, along with the mime types it is available as.
+pub struct SelectionOffer<P: SelectionProtocol> {
+    pub offer: Proxy<P::Offer>,
+    pub mime_types: Arc<Mutex<Vec<String>>>,
+}
+
+impl<P: SelectionProtocol> SelectionOffer<P> {
+    fn new(offer: NewProxy<P::Offer>) -> SelectionOffer<P> {
+        let mime_types = Arc::new(Mutex::new(Vec::new()));
+        let offer = P::implement_offer(offer, mime_types.clone());
+        SelectionOffer { offer, mime_types }
+    }
+
+    // Reads the data as the first of `mime_types` that is offered.
+    //
+    // This blocks until the other client has written all of it, or stops writing for a second.
+    pub fn receive(&self, display: &Display, mime_types: &[&str]) -> Option<Vec<u8>> {
+        let mime_type = pick_mime_type(&self.mime_types.lock().unwrap(), mime_types)?;
+        receive_through_pipe(display, |fd| P::receive(&self.offer, mime_type, fd))
+    }
+
+    // Asks for the data as the first of `mime_types` that is offered, without waiting for it.
+    pub fn start_receive(&self, display: &Display, mime_types: &[&str]) -> Option<PipeReader> {
+        let mime_type = pick_mime_type(&self.mime_types.lock().unwrap(), mime_types)?;
+        PipeReader::new(display, |fd| P::receive(&self.offer, mime_type, fd))
+    }
+}
+
+impl<P: SelectionProtocol> Drop for SelectionOffer<P> {
+    fn drop(&mut self) {
+        P::destroy_offer(&self.offer);
+    }
+}
+
+pub struct DeviceState<P: SelectionProtocol> {
+    // Offers announced to the device, which the following event refers to.
+    new_offers: Vec<SelectionOffer<P>>,
+    pub selection: Option<SelectionOffer<P>>,
+}
+
+impl<P: SelectionProtocol> DeviceState<P> {
+    fn new() -> DeviceState<P> {
+        DeviceState {
+            new_offers: Vec::new(),
+            selection: None,
+        }
+    }
+
+    pub fn add_offer(&mut self, offer: NewProxy<P::Offer>) {
+        self.new_offers.push(SelectionOffer::new(offer));
+    }
+
+    pub fn take_offer(&mut self, offer: &Proxy<P::Offer>) -> Option<SelectionOffer<P>> {
+        let idx = self.new_offers.iter().position(|o| o.offer.equals(offer))?;
+        Some(self.new_offers.swap_remove(idx))
+    }
+
+    pub fn set_selection(&mut self, offer: Option<Proxy<P::Offer>>) {
+        let selection = offer.and_then(|offer| self.take_offer(&offer));
+        self.selection = selection;
+    }
+}
+
+pub struct SeatDevice<P: SelectionProtocol> {
+    // The name of the seat's global.
+    pub seat_id: u32,
+    pub device: Proxy<P::Device>,
+    state: Arc<Mutex<DeviceState<P>>>,
+    // The serial of the last input event on this seat, which setting the selection requires.
+    pub serial: Arc<Mutex<u32>>,
+}
+
+// A selection, shared between the event loop and the windows.
+pub struct Selection<P: SelectionProtocol> {
+    display: Arc<Display>,
+    pub protocol: P,
+    pub manager: Option<Proxy<P::Manager>>,
+    // The seats we know of, since the manager can be announced after them.
+    seats: Vec<(u32, Proxy<wl_seat::WlSeat>, Arc<Mutex<u32>>)>,
+    pub devices: Vec<SeatDevice<P>>,
+    // The selection we set and its contents, until another client replaces it.
+    source: Arc<Mutex<Option<(Proxy<P::Source>, Arc<ClipboardContents>)>>>,
+}
+
+impl<P: SelectionProtocol> Selection<P> {
+    pub fn new(display: Arc<Display>, protocol: P) -> Selection<P> {
+        Selection {
+            display,
+            protocol,
+            manager: None,
+            seats: Vec::new(),
+            devices: Vec::new(),
+            source: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn set_manager(&mut self, manager: Proxy<P::Manager>) {
+        self.manager = Some(manager);
+        for (id, seat, serial) in self.seats.clone() {
+            self.new_device(id, &seat, serial);
+        }
+    }
+
+    pub fn new_seat(&mut self, id: u32, seat: &Proxy<wl_seat::WlSeat>, serial: Arc<Mutex<u32>>) {
+        self.seats.push((id, seat.clone(), serial.clone()));
+        self.new_device(id, seat, serial);
+    }
+
+    pub fn remove_seat(&mut self, id: u32) {
+        self.seats.retain(|&(i, _, _)| i != id);
+        if let Some(idx) = self.devices.iter().position(|d| d.seat_id == id) {
+            P::destroy_device(&self.devices.swap_remove(idx).device);
+        }
+    }
+
+    fn new_device(&mut self, id: u32, seat: &Proxy<wl_seat::WlSeat>, serial: Arc<Mutex<u32>>) {
+        let device = match self.manager {
+            Some(ref manager) => P::get_device(manager, seat),
+            None => return,
+        };
+        let state = Arc::new(Mutex::new(DeviceState::new()));
+        let device = self.protocol.implement_device(device, state.clone());
+        self.devices.push(SeatDevice { seat_id: id, device, state, serial });
+    }
+
+    pub fn set_contents(&mut self, contents: ClipboardContents) {
+        let manager = match self.manager {
+            Some(ref manager) => manager,
+            None => return,
+        };
+        let contents = Arc::new(contents);
+        let current_source = self.source.clone();
+        let source = P::implement_source(P::create_source(manager), contents.clone(), move |source| {
+            let mut current_source = current_source.lock().unwrap();
+            let is_current = current_source
+                .as_ref()
+                .map(|&(ref current, _)| current.equals(source))
+                .unwrap_or(false);
+            if is_current {
+                *current_source = None;
+            }
+        });
+        for &(ref mime_type, _) in contents.iter() {
+            P::offer(&source, mime_type.clone());
+        }
+        for device in &self.devices {
+            P::set_selection(&device.device, &source, *device.serial.lock().unwrap());
+        }
+        *self.source.lock().unwrap() = Some((source, contents));
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.set_contents(clipboard::text_contents(text));
+    }
+
+    pub fn get_mime_types(&self) -> Vec<String> {
+        if let Some((_, ref contents)) = *self.source.lock().unwrap() {
+            return contents.iter().map(|&(ref mime_type, _)| mime_type.clone()).collect();
+        }
+        self.devices.iter().filter_map(|device| {
+            let state = device.state.lock().unwrap();
+            let mime_types = state.selection.as_ref()?.mime_types.lock().unwrap().clone();
+            Some(mime_types)
+        }).next().unwrap_or_default()
+    }
+
+    pub fn get_data(&self, mime_type: &str) -> Option<Vec<u8>> {
+        self.get_first_of(&[mime_type])
+    }
+
+    pub fn get_text(&self) -> Option<String> {
+        self.get_first_of(TEXT_MIME_TYPES).and_then(|data| String::from_utf8(data).ok())
+    }
+
+    fn get_first_of(&self, mime_types: &[&str]) -> Option<Vec<u8>> {
+        // Our own selection has to be answered by us, so we can't wait on it.
+        if let Some((_, ref contents)) = *self.source.lock().unwrap() {
+            return clipboard::find_data(contents, mime_types);
+        }
+        self.devices.iter().filter_map(|device| {
+            let state = device.state.lock().unwrap();
+            state.selection.as_ref()?.receive(&self.display, mime_types)
+        }).next()
+    }
+}
+
+// Returns the first of `wanted` that is in `offered`.
+pub fn pick_mime_type(offered: &[String], wanted: &[&str]) -> Option<String> {
+    wanted.iter().find(|&&m| offered.iter().any(|o| o == m)).map(|m| m.to_string())
+}
+
+// Data that another client is writing to a pipe.
+pub struct PipeReader {
+    pipe: File,
+    // What's been read so far.
+    data: Vec<u8>,
+}
+
+impl PipeReader {
+    // Creates a pipe and passes its write end to `request`.
+    pub fn new<F>(display: &Display, request: F) -> Option<PipeReader>
+    where
+        F: FnOnce(RawFd),
+    {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return None;
+        }
+        let pipe = unsafe { File::from_raw_fd(fds[0]) };
+        request(fds[1]);
+        // The compositor has its own copy of the write end once the request is sent.
+        let flushed = display.flush();
+        unsafe { libc::close(fds[1]) };
+        flushed.ok()?;
+        Some(PipeReader { pipe, data: Vec::new() })
+    }
+
+    pub fn fd(&self) -> RawFd {
+        self.pipe.as_raw_fd()
+    }
+
+    // How much has been read so far.
+    pub fn received(&self) -> usize {
+        self.data.len()
+    }
+
+    // Reads what's been written, waiting up to `timeout_ms` for each part of it.
+    //
+    // This returns all of the data once the other client has closed the pipe, or `None` if it
+    // hasn't yet.
+    pub fn read(&mut self, timeout_ms: libc::c_int) -> io::Result<Option<Vec<u8>>> {
+        let mut buf = [0; 4096];
+        loop {
+            let mut poll_fd = libc::pollfd {
+                fd: self.pipe.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            match unsafe { libc::poll(&mut poll_fd, 1, timeout_ms) } {
+                0 => return Ok(None),
+                n if n < 0 => {
+                    let err = io::Error::last_os_error();
+                    if err.kind() != io::ErrorKind::Interrupted {
+                        return Err(err);
+                    }
+                }
+                _ => match self.pipe.read(&mut buf) {
+                    Ok(0) => return Ok(Some(mem::replace(&mut self.data, Vec::new()))),
+                    Ok(len) => self.data.extend_from_slice(&buf[..len]),
+                    Err(ref e) if e.kind() == io::ErrorKind::Interrupted => (),
+                    Err(e) => return Err(e),
+                },
+            }
+        }
+    }
+}
+
+// Creates a pipe, passes its write end to `request` and reads everything written to it.
+//
+// This gives up if the other client stops writing without closing the pipe.
+pub fn receive_through_pipe<F>(display: &Display, request: F) -> Option<Vec<u8>>
+where
+    F: FnOnce(RawFd),
+{
+    PipeReader::new(display, request)?.read(RECEIVE_TIMEOUT_MS).ok()?
+}
+
+// Writes `data` to a pipe that another client is reading from, then closes it.
+pub fn send_through_pipe(fd: RawFd, data: &[u8]) {
+    let mut pipe = unsafe { File::from_raw_fd(fd) };
+    let _ = pipe.write_all(data);
+}
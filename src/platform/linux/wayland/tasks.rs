@@ -0,0 +1,177 @@
+use std::mem;
+use std::os::unix::io::RawFd;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use libc;
+
+use sctk::reexports::client::Display;
+use sctk::reexports::client::protocol::wl_display::RequestsTrait as DisplayRequests;
+
+use super::event_loop::EventsLoopSink;
+use super::selection::{PipeReader, RECEIVE_TIMEOUT_MS};
+
+// A pipe another client is writing to, which we read as the data arrives.
+struct PendingRead {
+    reader: PipeReader,
+    // When we give up, unless more data arrives before then.
+    deadline: Instant,
+    // Called once, with the data or `None` if the other client stopped writing.
+    done: Box<FnMut(Option<Vec<u8>>, &mut EventsLoopSink) + Send>,
+}
+
+struct Timer {
+    id: u64,
+    deadline: Instant,
+    // Returns how long until it's called again, if it should be.
+    callback: Box<FnMut() -> Option<Duration> + Send>,
+}
+
+// What the event loop waits for besides the Wayland connection, shared with the handlers and
+// windows that need something done later without blocking it.
+pub struct LoopTasks {
+    display: Arc<Display>,
+    reads: Vec<PendingRead>,
+    timers: Vec<Timer>,
+    next_timer_id: u64,
+    // Timers removed while they were running, which mustn't be rescheduled.
+    cancelled: Vec<u64>,
+}
+
+impl LoopTasks {
+    pub fn new(display: Arc<Display>) -> LoopTasks {
+        LoopTasks {
+            display,
+            reads: Vec::new(),
+            timers: Vec::new(),
+            next_timer_id: 0,
+            cancelled: Vec::new(),
+        }
+    }
+
+    // Reads the pipe as the data arrives, and calls `done` with all of it once the other client
+    // has closed the pipe.
+    pub fn read_pipe<F>(&mut self, reader: PipeReader, done: F)
+    where
+        F: FnMut(Option<Vec<u8>>, &mut EventsLoopSink) + Send + 'static,
+    {
+        self.reads.push(PendingRead {
+            reader,
+            deadline: Instant::now() + Duration::from_millis(RECEIVE_TIMEOUT_MS as u64),
+            done: Box::new(done),
+        });
+        self.wake();
+    }
+
+    // Calls `callback` after `delay`, and again after each delay it returns.
+    pub fn add_timer<F>(&mut self, delay: Duration, callback: F) -> u64
+    where
+        F: FnMut() -> Option<Duration> + Send + 'static,
+    {
+        let id = self.next_timer_id;
+        self.next_timer_id += 1;
+        self.timers.push(Timer {
+            id,
+            deadline: Instant::now() + delay,
+            callback: Box::new(callback),
+        });
+        self.wake();
+        id
+    }
+
+    pub fn remove_timer(&mut self, id: u64) {
+        let count = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        if self.timers.len() == count {
+            self.cancelled.push(id);
+        }
+    }
+
+    // The event loop might be waiting with a longer timeout, or none, so we have the compositor
+    // wake it up.
+    fn wake(&self) {
+        let _ = self.display.sync();
+        let _ = self.display.flush();
+    }
+
+    // The pipes to wait for, and how long until the next deadline.
+    pub fn poll_fds(&self) -> (Vec<RawFd>, Option<Duration>) {
+        let fds = self.reads.iter().map(|read| read.reader.fd()).collect();
+        let deadline = self.reads
+            .iter()
+            .map(|read| read.deadline)
+            .chain(self.timers.iter().map(|timer| timer.deadline))
+            .min();
+        let now = Instant::now();
+        let timeout = deadline.map(|deadline| {
+            if deadline > now { deadline - now } else { Duration::from_millis(0) }
+        });
+        (fds, timeout)
+    }
+}
+
+// Reads what has arrived in the pipes and runs the timers that are due.
+//
+// This is done without holding the lock, as the callbacks take locks that are held by the
+// windows while they add tasks.
+pub fn run_tasks(tasks: &Mutex<LoopTasks>, sink: &Mutex<EventsLoopSink>) {
+    let now = Instant::now();
+    let (reads, due_timers) = {
+        let mut tasks = tasks.lock().unwrap();
+        let reads = mem::replace(&mut tasks.reads, Vec::new());
+        let (due_timers, timers) = mem::replace(&mut tasks.timers, Vec::new())
+            .into_iter()
+            .partition::<Vec<_>, _>(|timer| timer.deadline <= now);
+        tasks.timers = timers;
+        (reads, due_timers)
+    };
+
+    let mut unfinished = Vec::new();
+    for mut read in reads {
+        let received = read.reader.received();
+        let result = match read.reader.read(0) {
+            Ok(Some(data)) => Some(data),
+            Ok(None) if read.reader.received() > received => {
+                read.deadline = Instant::now() + Duration::from_millis(RECEIVE_TIMEOUT_MS as u64);
+                unfinished.push(read);
+                continue;
+            }
+            Ok(None) if read.deadline > now => {
+                unfinished.push(read);
+                continue;
+            }
+            // The other client stopped writing, or the pipe broke.
+            _ => None,
+        };
+        (read.done)(result, &mut sink.lock().unwrap());
+    }
+
+    let mut rescheduled = Vec::new();
+    for mut timer in due_timers {
+        if let Some(delay) = (timer.callback)() {
+            timer.deadline = Instant::now() + delay;
+            rescheduled.push(timer);
+        }
+    }
+
+    let mut tasks = tasks.lock().unwrap();
+    let cancelled = mem::replace(&mut tasks.cancelled, Vec::new());
+    tasks.reads.extend(unfinished);
+    tasks.timers.extend(rescheduled.into_iter().filter(|timer| !cancelled.contains(&timer.id)));
+}
+
+// Waits up to `timeout` for any of `fds` to be readable, returning which are.
+pub fn poll(fds: &[RawFd], timeout: Option<Duration>) -> Vec<bool> {
+    let mut poll_fds: Vec<_> = fds
+        .iter()
+        .map(|&fd| libc::pollfd { fd, events: libc::POLLIN, revents: 0 })
+        .collect();
+    // Rounded up, so we don't wake up just before a deadline.
+    let timeout_ms = match timeout {
+        Some(timeout) => (timeout.as_secs() * 1000 + (timeout.subsec_nanos() as u64 + 999_999) / 1_000_000) as libc::c_int,
+        None => -1,
+    };
+    // Being interrupted by a signal is like timing out, and the caller loops anyway.
+    unsafe { libc::poll(poll_fds.as_mut_ptr(), poll_fds.len() as libc::nfds_t, timeout_ms) };
+    poll_fds.iter().map(|poll_fd| poll_fd.revents != 0).collect()
+}
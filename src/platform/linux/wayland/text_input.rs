@@ -0,0 +1,159 @@
+use std::sync::{Arc, Mutex};
+
+use WindowEvent;
+
+use super::{make_wid, EventsLoopSink, WindowId};
+use super::window::WindowStore;
+
+use sctk::reexports::client::{NewProxy, Proxy};
+use sctk::reexports::protocols::unstable::text_input::v3::client::zwp_text_input_v3::{
+    self,
+    Event as TextInputEvent,
+    ZwpTextInputV3,
+};
+use self::zwp_text_input_v3::RequestsTrait as TextInputRequests;
+
+// The IME state of a window, shared between the `Window` and the text inputs focused on it.
+pub struct WindowIme {
+    allowed: bool,
+    cursor_area: (i32, i32, i32, i32),
+    // The text inputs of the seats whose keyboard focus is on this window.
+    text_inputs: Vec<Proxy<ZwpTextInputV3>>,
+}
+
+impl WindowIme {
+    pub fn new() -> WindowIme {
+        WindowIme {
+            allowed: true,
+            cursor_area: (0, 0, 0, 0),
+            text_inputs: Vec::new(),
+        }
+    }
+
+    pub fn set_allowed(&mut self, allowed: bool) {
+        if self.allowed == allowed {
+            return;
+        }
+        self.allowed = allowed;
+        for text_input in &self.text_inputs {
+            if allowed {
+                enable(text_input, self.cursor_area);
+            } else {
+                disable(text_input);
+            }
+        }
+    }
+
+    pub fn set_cursor_area(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        self.cursor_area = (x, y, width, height);
+        if !self.allowed {
+            return;
+        }
+        for text_input in &self.text_inputs {
+            text_input.set_cursor_rectangle(x, y, width, height);
+            text_input.commit();
+        }
+    }
+}
+
+fn enable(text_input: &Proxy<ZwpTextInputV3>, (x, y, width, height): (i32, i32, i32, i32)) {
+    text_input.enable();
+    text_input.set_cursor_rectangle(x, y, width, height);
+    text_input.commit();
+}
+
+fn disable(text_input: &Proxy<ZwpTextInputV3>) {
+    text_input.disable();
+    text_input.commit();
+}
+
+pub fn implement_text_input(
+    text_input: NewProxy<ZwpTextInputV3>,
+    sink: Arc<Mutex<EventsLoopSink>>,
+    store: Arc<Mutex<WindowStore>>,
+) -> Proxy<ZwpTextInputV3> {
+    let mut focus: Option<(WindowId, Arc<Mutex<WindowIme>>)> = None;
+    // Pre-edit and commit strings are double-buffered until `done`.
+    let mut pending_preedit: Option<(String, i32, i32)> = None;
+    let mut pending_commit: Option<String> = None;
+    let mut preedit_shown = false;
+
+    text_input.implement(move |evt, text_input: Proxy<ZwpTextInputV3>| {
+        let mut sink = sink.lock().unwrap();
+        match evt {
+            TextInputEvent::Enter { surface } => {
+                let ime = match store.lock().unwrap().find_ime(&surface) {
+                    Some(ime) => ime,
+                    None => return,
+                };
+                {
+                    let mut window_ime = ime.lock().unwrap();
+                    window_ime.text_inputs.push(text_input.clone());
+                    if window_ime.allowed {
+                        enable(&text_input, window_ime.cursor_area);
+                    }
+                }
+                focus = Some((make_wid(&surface), ime));
+            }
+            TextInputEvent::Leave { .. } => {
+                if let Some((wid, ime)) = focus.take() {
+                    ime.lock().unwrap().text_inputs.retain(|t| !t.equals(&text_input));
+                    disable(&text_input);
+                    if preedit_shown {
+                        sink.send_event(
+                            WindowEvent::ImePreedit { text: String::new(), cursor_range: None },
+                            wid,
+                        );
+                    }
+                }
+                pending_preedit = None;
+                pending_commit = None;
+                preedit_shown = false;
+            }
+            TextInputEvent::PreeditString { text, cursor_begin, cursor_end } => {
+                pending_preedit = Some((text.unwrap_or_default(), cursor_begin, cursor_end));
+            }
+            TextInputEvent::CommitString { text } => {
+                pending_commit = text;
+            }
+            // We never send the surrounding text, so there is nothing to delete.
+            TextInputEvent::DeleteSurroundingText { .. } => (),
+            TextInputEvent::Done { .. } => {
+                let wid = match focus {
+                    Some((wid, _)) => wid,
+                    None => return,
+                };
+
+                if let Some(text) = pending_commit.take() {
+                    // The commit replaces the pre-edit.
+                    if preedit_shown {
+                        sink.send_event(
+                            WindowEvent::ImePreedit { text: String::new(), cursor_range: None },
+                            wid,
+                        );
+                        preedit_shown = false;
+                    }
+                    sink.send_event(WindowEvent::ImeCommit(text.clone()), wid);
+                    for chr in text.chars() {
+                        sink.send_event(WindowEvent::ReceivedCharacter(chr), wid);
+                    }
+                }
+
+                // No pre-edit string before `done` means the pre-edit is now empty.
+                let (text, cursor_begin, cursor_end) = pending_preedit
+                    .take()
+                    .unwrap_or_else(|| (String::new(), -1, -1));
+                if !text.is_empty() || preedit_shown {
+                    // A negative cursor position asks us to hide the cursor.
+                    let cursor_range = if cursor_begin < 0 || cursor_end < 0 {
+                        None
+                    } else {
+                        Some((cursor_begin as usize, cursor_end as usize))
+                    };
+                    preedit_shown = !text.is_empty();
+                    sink.send_event(WindowEvent::ImePreedit { text, cursor_range }, wid);
+                }
+            }
+        }
+    })
+}
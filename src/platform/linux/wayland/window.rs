@@ -1,19 +1,28 @@
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex, Weak};
 
-use {CreationError, MouseCursor, WindowAttributes};
+use {CreationError, CursorGrabMode, CustomCursor, DndAction, MouseCursor, WindowAttributes};
 use dpi::{LogicalPosition, LogicalSize};
 use platform::MonitorId as PlatformMonitorId;
 use window::MonitorId as RootMonitorId;
 
 use sctk::window::{BasicFrame, Event as WEvent, Window as SWindow};
 use sctk::reexports::client::{Display, Proxy};
-use sctk::reexports::client::protocol::{wl_seat, wl_surface, wl_output};
+use sctk::reexports::client::protocol::{wl_output, wl_pointer, wl_seat, wl_surface};
 use sctk::reexports::client::protocol::wl_compositor::RequestsTrait as CompositorRequests;
 use sctk::reexports::client::protocol::wl_surface::RequestsTrait as SurfaceRequests;
 use sctk::output::OutputMgr;
 
 use super::{make_wid, EventsLoop, MonitorId, WindowId};
+use super::cursor::WindowCursor;
+use super::pointer::PointerTracker;
+use super::pointer_constraints::{Pointers, WindowGrab};
+use super::text_input::WindowIme;
+use super::data_device::{Clipboard, DropTarget};
+use super::primary_selection::PrimarySelection;
+use super::super::clipboard::ClipboardContents;
+use super::super::cursor::Cursor;
+use super::super::dnd::DropResponse;
 use platform::platform::wayland::event_loop::{get_available_monitors, get_primary_monitor};
 
 pub struct Window {
@@ -25,6 +34,14 @@ pub struct Window {
     kill_switch: (Arc<Mutex<bool>>, Arc<Mutex<bool>>),
     display: Arc<Display>,
     need_frame_refresh: Arc<Mutex<bool>>,
+    ime: Arc<Mutex<WindowIme>>,
+    drop_target: Arc<Mutex<DropTarget>>,
+    cursor: Arc<Mutex<WindowCursor>>,
+    grab: Arc<Mutex<WindowGrab>>,
+    pointers: Arc<Mutex<Pointers>>,
+    pointer_tracker: Arc<Mutex<PointerTracker>>,
+    clipboard: Arc<Mutex<Clipboard>>,
+    primary_selection: Arc<Mutex<PrimarySelection>>,
 }
 
 impl Window {
@@ -135,6 +152,15 @@ impl Window {
         let kill_switch = Arc::new(Mutex::new(false));
         let need_frame_refresh = Arc::new(Mutex::new(true));
         let frame = Arc::new(Mutex::new(frame));
+        let ime = Arc::new(Mutex::new(WindowIme::new()));
+        let drop_target = Arc::new(Mutex::new(DropTarget::default()));
+        let cursor = Arc::new(Mutex::new(WindowCursor::new(
+            &evlp.tasks,
+            &evlp.env.compositor,
+            &evlp.env.shm,
+            evlp.cursor_themes.clone(),
+        )));
+        let grab = Arc::new(Mutex::new(WindowGrab::new(&evlp.env.compositor, &surface)));
 
         evlp.store.lock().unwrap().windows.push(InternalWindow {
             closed: false,
@@ -147,6 +173,10 @@ impl Window {
             frame: Arc::downgrade(&frame),
             current_dpi: 1,
             new_dpi: None,
+            ime: ime.clone(),
+            drop_target: drop_target.clone(),
+            cursor: cursor.clone(),
+            grab: grab.clone(),
         });
         evlp.evq.borrow_mut().sync_roundtrip().unwrap();
 
@@ -159,6 +189,14 @@ impl Window {
             size: size,
             kill_switch: (kill_switch, evlp.cleanup_needed.clone()),
             need_frame_refresh: need_frame_refresh,
+            ime: ime,
+            drop_target: drop_target,
+            cursor: cursor,
+            grab: grab,
+            pointers: evlp.pointers.clone(),
+            pointer_tracker: evlp.pointer_tracker.clone(),
+            clipboard: evlp.clipboard.clone(),
+            primary_selection: evlp.primary_selection.clone(),
         })
     }
 
@@ -265,18 +303,45 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_cursor(&self, _cursor: MouseCursor) {
-        // TODO
+    pub fn set_cursor(&self, cursor: MouseCursor) {
+        self.cursor.lock().unwrap().set_cursor(Cursor::Named(cursor));
+    }
+
+    #[inline]
+    pub fn set_custom_cursor(&self, cursor: CustomCursor) {
+        self.cursor.lock().unwrap().set_cursor(Cursor::Custom(cursor));
+    }
+
+    #[inline]
+    pub fn hide_cursor(&self, hide: bool) {
+        self.cursor.lock().unwrap().set_hidden(hide);
     }
 
     #[inline]
-    pub fn hide_cursor(&self, _hide: bool) {
-        // TODO: This isn't possible on Wayland yet
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), String> {
+        let pointers = self.pointers.lock().unwrap();
+        self.grab.lock().unwrap().set_grab(mode, None, &pointers)
     }
 
     #[inline]
-    pub fn grab_cursor(&self, _grab: bool) -> Result<(), String> {
-        Err("Cursor grabbing is not yet possible on Wayland.".to_owned())
+    pub fn confine_cursor_to(&self, area: Option<(LogicalPosition, LogicalSize)>) -> Result<(), String> {
+        let area = area.map(|(position, size)| {
+            let (x, y) = position.into();
+            let (width, height): (u32, u32) = size.into();
+            (x, y, width as i32, height as i32)
+        });
+        let pointers = self.pointers.lock().unwrap();
+        self.grab.lock().unwrap().set_area(area, &pointers)
+    }
+
+    #[inline]
+    pub fn get_cursor_position(&self) -> Option<LogicalPosition> {
+        // The position is only known while the pointer is over the window.
+        self.pointer_tracker
+            .lock()
+            .unwrap()
+            .position_in(self.id())
+            .map(Into::into)
     }
 
     #[inline]
@@ -284,6 +349,86 @@ impl Window {
         Err("Setting the cursor position is not yet possible on Wayland.".to_owned())
     }
 
+    #[inline]
+    pub fn set_ime_spot(&self, spot: LogicalPosition) {
+        let (x, y) = spot.into();
+        self.ime.lock().unwrap().set_cursor_area(x, y, 0, 0);
+    }
+
+    #[inline]
+    pub fn set_ime_cursor_area(&self, position: LogicalPosition, size: LogicalSize) {
+        let (x, y) = position.into();
+        let (width, height): (u32, u32) = size.into();
+        self.ime.lock().unwrap().set_cursor_area(x, y, width as i32, height as i32);
+    }
+
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        self.ime.lock().unwrap().set_allowed(allowed);
+    }
+
+    #[inline]
+    pub fn set_clipboard_text(&self, text: &str) -> Result<(), String> {
+        self.clipboard.lock().unwrap().set_text(text);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn get_clipboard_text(&self) -> Option<String> {
+        self.clipboard.lock().unwrap().get_text()
+    }
+
+    #[inline]
+    pub fn set_clipboard_contents(&self, contents: ClipboardContents) -> Result<(), String> {
+        self.clipboard.lock().unwrap().set_contents(contents);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn get_clipboard_mime_types(&self) -> Vec<String> {
+        self.clipboard.lock().unwrap().get_mime_types()
+    }
+
+    #[inline]
+    pub fn get_clipboard_data(&self, mime_type: &str) -> Option<Vec<u8>> {
+        self.clipboard.lock().unwrap().get_data(mime_type)
+    }
+
+    #[inline]
+    pub fn set_primary_selection_text(&self, text: &str) -> Result<(), String> {
+        self.primary_selection.lock().unwrap().set_text(text);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn get_primary_selection_text(&self) -> Option<String> {
+        self.primary_selection.lock().unwrap().get_text()
+    }
+
+    #[inline]
+    pub fn start_drag(&self, contents: ClipboardContents, actions: &[DndAction]) -> Result<(), String> {
+        let wid = make_wid(&self.surface);
+        let (serial, seat_id) = match self.pointer_tracker.lock().unwrap().press_on(wid) {
+            Some(press) => press,
+            None => return Err("Drag could not be started: no button is held down over the window".to_owned()),
+        };
+        self.clipboard
+            .lock()
+            .unwrap()
+            .start_drag(&self.surface, wid, seat_id, serial, contents, actions)
+    }
+
+    #[inline]
+    pub fn set_accepted_drop_types(&self, mime_types: &[&str]) {
+        self.drop_target.lock().unwrap().accepted_types = mime_types.iter().map(|&m| m.to_owned()).collect();
+    }
+
+    #[inline]
+    pub fn set_drop_response(&self, action: Option<DndAction>, area: Option<(LogicalPosition, LogicalSize)>) {
+        // There's no way to tell the compositor where the answer holds.
+        self.drop_target.lock().unwrap().set_response(DropResponse { action, area });
+    }
+
     pub fn get_display(&self) -> &Display {
         &*self.display
     }
@@ -310,6 +455,8 @@ impl Window {
 
 impl Drop for Window {
     fn drop(&mut self) {
+        let pointers = self.pointers.lock().unwrap();
+        let _ = self.grab.lock().unwrap().set_grab(CursorGrabMode::None, None, &pointers);
         *(self.kill_switch.0.lock().unwrap()) = true;
         *(self.kill_switch.1.lock().unwrap()) = true;
     }
@@ -329,7 +476,11 @@ struct InternalWindow {
     kill_switch: Arc<Mutex<bool>>,
     frame: Weak<Mutex<SWindow<BasicFrame>>>,
     current_dpi: i32,
-    new_dpi: Option<i32>
+    new_dpi: Option<i32>,
+    ime: Arc<Mutex<WindowIme>>,
+    drop_target: Arc<Mutex<DropTarget>>,
+    cursor: Arc<Mutex<WindowCursor>>,
+    grab: Arc<Mutex<WindowGrab>>,
 }
 
 pub struct WindowStore {
@@ -352,6 +503,30 @@ impl WindowStore {
         None
     }
 
+    pub fn find_ime(&self, surface: &Proxy<wl_surface::WlSurface>) -> Option<Arc<Mutex<WindowIme>>> {
+        self.windows
+            .iter()
+            .find(|window| surface.equals(&window.surface))
+            .map(|window| window.ime.clone())
+    }
+
+    pub fn find_cursor(&self, surface: &Proxy<wl_surface::WlSurface>) -> Option<Arc<Mutex<WindowCursor>>> {
+        self.windows
+            .iter()
+            .find(|window| surface.equals(&window.surface))
+            .map(|window| window.cursor.clone())
+    }
+
+    pub fn find_drop_target(
+        &self,
+        surface: &Proxy<wl_surface::WlSurface>,
+    ) -> Option<Arc<Mutex<DropTarget>>> {
+        self.windows
+            .iter()
+            .find(|window| surface.equals(&window.surface))
+            .map(|window| window.drop_target.clone())
+    }
+
     pub fn cleanup(&mut self) -> Vec<WindowId> {
         let mut pruned = Vec::new();
         self.windows.retain(|w| {
@@ -367,6 +542,12 @@ impl WindowStore {
         pruned
     }
 
+    pub fn reload_cursors(&self) {
+        for window in &self.windows {
+            window.cursor.lock().unwrap().reload();
+        }
+    }
+
     pub fn new_seat(&self, seat: &Proxy<wl_seat::WlSeat>) {
         for window in &self.windows {
             if let Some(w) = window.frame.upgrade() {
@@ -375,10 +556,23 @@ impl WindowStore {
         }
     }
 
+    pub fn new_pointer(&self, pointer: &Proxy<wl_pointer::WlPointer>, pointers: &Pointers) {
+        for window in &self.windows {
+            window.grab.lock().unwrap().pointer_added(pointer, pointers);
+        }
+    }
+
+    pub fn remove_pointer(&self, pointer: &Proxy<wl_pointer::WlPointer>) {
+        for window in &self.windows {
+            window.grab.lock().unwrap().pointer_removed(pointer);
+        }
+    }
+
     fn dpi_change(&mut self, surface: &Proxy<wl_surface::WlSurface>, new: i32) {
         for window in &mut self.windows {
             if surface.equals(&window.surface) {
                 window.new_dpi = Some(new);
+                window.cursor.lock().unwrap().set_scale(new);
             }
         }
     }
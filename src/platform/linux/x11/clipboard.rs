@@ -0,0 +1,535 @@
+use std::{cmp, io, mem};
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::os::raw::*;
+use std::os::unix::io::RawFd;
+
+use libc;
+use parking_lot::Mutex;
+
+use super::{ffi, util, XConnection, XError};
+use super::super::clipboard::{self, ClipboardContents, TEXT_MIME_TYPES};
+
+// How long we wait for the selection owner to send the next part of the data before giving up.
+const CONVERSION_TIMEOUT_MS: u64 = 1000;
+
+#[derive(Debug)]
+pub struct ClipboardAtoms {
+    pub clipboard: ffi::Atom,
+    pub targets: ffi::Atom,
+    pub multiple: ffi::Atom,
+    pub timestamp: ffi::Atom,
+    pub incr: ffi::Atom,
+    // The property selection owners store the converted data in.
+    pub transfer: ffi::Atom,
+}
+
+impl ClipboardAtoms {
+    pub fn new(xconn: &Arc<XConnection>) -> Result<Self, XError> {
+        let names = [
+            b"CLIPBOARD\0".as_ptr() as *mut c_char,
+            b"TARGETS\0".as_ptr() as *mut c_char,
+            b"MULTIPLE\0".as_ptr() as *mut c_char,
+            b"TIMESTAMP\0".as_ptr() as *mut c_char,
+            b"INCR\0".as_ptr() as *mut c_char,
+            b"_WINIT_SELECTION\0".as_ptr() as *mut c_char,
+        ];
+        let atoms = unsafe { xconn.get_atoms(&names) }?;
+        Ok(ClipboardAtoms {
+            clipboard: atoms[0],
+            targets: atoms[1],
+            multiple: atoms[2],
+            timestamp: atoms[3],
+            incr: atoms[4],
+            transfer: atoms[5],
+        })
+    }
+}
+
+// The targets we offer a selection as, with their names and data.
+type OwnedContents = Vec<(ffi::Atom, String, clipboard::ClipboardData)>;
+
+// Data we're sending to another client in chunks, using the `INCR` mechanism.
+struct IncrTransfer {
+    requestor: ffi::Window,
+    property: ffi::Atom,
+    target: ffi::Atom,
+    data: Vec<u8>,
+    // How much of `data` has been sent.
+    sent: usize,
+    // The events the requestor's window was selected for before the transfer, which might be
+    // one of our own windows.
+    event_mask: c_long,
+}
+
+pub struct Clipboard {
+    xconn: Arc<XConnection>,
+    pub atoms: ClipboardAtoms,
+    // Owns the selections we set, and receives the ones we request.
+    pub window: ffi::Window,
+    // The contents of each selection we currently own.
+    contents: Mutex<HashMap<ffi::Atom, Arc<OwnedContents>>>,
+    // Events for `window` that the event loop received while we were waiting for them.
+    pending: Mutex<Vec<ffi::XEvent>>,
+    // Written to whenever an event is added to `pending`, as the event loop might be reading the
+    // connection on another thread while we wait.
+    pending_pipe: (RawFd, RawFd),
+    // The time of the last input event, which we take and request selections at.
+    time: Mutex<ffi::Time>,
+    incr_transfers: Mutex<Vec<IncrTransfer>>,
+    // Data larger than this is sent using `INCR`.
+    max_chunk_size: usize,
+}
+
+impl Clipboard {
+    pub fn new(xconn: Arc<XConnection>, window: ffi::Window) -> Result<Self, XError> {
+        let atoms = ClipboardAtoms::new(&xconn)?;
+        let max_chunk_size = unsafe {
+            // We leave room for the rest of the `ChangeProperty` request.
+            let max_request_size = (xconn.xlib.XMaxRequestSize)(xconn.display) as usize * 4;
+            cmp::max(max_request_size, 4096) - 1024
+        };
+        unsafe {
+            // This is how we're told about each part of an `INCR` transfer.
+            (xconn.xlib.XSelectInput)(xconn.display, window, ffi::PropertyChangeMask);
+        }
+        xconn.check_errors()?;
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_NONBLOCK | libc::O_CLOEXEC) } != 0 {
+            panic!("Failed to create the clipboard's pipe: {}", io::Error::last_os_error());
+        }
+        Ok(Clipboard {
+            xconn,
+            atoms,
+            window,
+            contents: Default::default(),
+            pending: Default::default(),
+            pending_pipe: (fds[0], fds[1]),
+            time: Mutex::new(ffi::CurrentTime),
+            incr_transfers: Default::default(),
+            max_chunk_size,
+        })
+    }
+
+    pub fn set_contents(&self, selection: ffi::Atom, contents: ClipboardContents) -> Result<(), String> {
+        let contents = contents
+            .into_iter()
+            .filter_map(|(mime_type, data)| {
+                let atom = self.xconn.get_atom(CString::new(mime_type.clone()).ok()?);
+                Some((atom, mime_type, data))
+            })
+            .collect();
+        self.contents.lock().insert(selection, Arc::new(contents));
+        unsafe {
+            (self.xconn.xlib.XSetSelectionOwner)(
+                self.xconn.display,
+                selection,
+                self.window,
+                self.time(),
+            );
+        }
+        // Another client can take the selection at a later time than ours, in which case the
+        // server ignores our request.
+        let owner = unsafe { (self.xconn.xlib.XGetSelectionOwner)(self.xconn.display, selection) };
+        let result = match self.xconn.check_errors() {
+            Err(err) => Err(format!("Failed to call XSetSelectionOwner: {:?}", err)),
+            Ok(()) if owner != self.window => Err("Failed to take ownership of the selection".to_owned()),
+            Ok(()) => Ok(()),
+        };
+        if result.is_err() {
+            self.contents.lock().remove(&selection);
+        }
+        result
+    }
+
+    pub fn set_text(&self, selection: ffi::Atom, text: &str) -> Result<(), String> {
+        self.set_contents(selection, clipboard::text_contents(text))
+    }
+
+    pub fn get_text(&self, selection: ffi::Atom) -> Option<String> {
+        TEXT_MIME_TYPES
+            .iter()
+            .filter_map(|mime_type| self.get_data(selection, mime_type))
+            .next()
+            .and_then(|data| String::from_utf8(data).ok())
+    }
+
+    // Returns the mime types the selection is offered as.
+    pub fn get_mime_types(&self, selection: ffi::Atom) -> Vec<String> {
+        if let Some(contents) = self.owned_contents(selection) {
+            return contents.iter().map(|&(_, ref mime_type, _)| mime_type.clone()).collect();
+        }
+
+        if !self.convert(selection, self.atoms.targets) {
+            return Vec::new();
+        }
+        let targets = match self.take_property::<ffi::Atom>(ffi::XA_ATOM) {
+            Some((_, targets)) => targets,
+            None => return Vec::new(),
+        };
+        targets
+            .into_iter()
+            .filter(|&atom| {
+                atom != self.atoms.targets
+                    && atom != self.atoms.multiple
+                    && atom != self.atoms.timestamp
+            })
+            .filter_map(|atom| self.xconn.get_atom_name(atom))
+            .collect()
+    }
+
+    // Returns the selection converted to `mime_type`.
+    //
+    // This blocks until the owner has sent all of it.
+    pub fn get_data(&self, selection: ffi::Atom, mime_type: &str) -> Option<Vec<u8>> {
+        // The owner would be us, and we can't answer our own request without running the event
+        // loop.
+        if let Some(contents) = self.owned_contents(selection) {
+            return contents
+                .iter()
+                .find(|&&(_, ref m, _)| m == mime_type)
+                .map(|&(_, _, ref data)| data.get().into_owned());
+        }
+
+        let target = self.xconn.get_atom(CString::new(mime_type).ok()?);
+        if !self.convert(selection, target) {
+            return None;
+        }
+        // The property being set was itself a change, which we don't want to mistake for the
+        // first chunk of an `INCR` transfer. The owner sets it before sending `SelectionNotify`,
+        // so it's already been received, and no chunk can arrive before we delete the property.
+        self.discard_events(ffi::PropertyNotify);
+        let (property_type, data) = self.take_property::<c_uchar>(target)?;
+        if property_type != self.atoms.incr {
+            return Some(data);
+        }
+
+        // Deleting the `INCR` property told the owner to send the first chunk, and each chunk we
+        // delete asks for the next, until an empty one marks the end.
+        let mut data = Vec::new();
+        loop {
+            let transfer = self.atoms.transfer;
+            self.wait_for_event(ffi::PropertyNotify, |event| {
+                let event: &ffi::XPropertyEvent = event.as_ref();
+                event.atom == transfer && event.state == ffi::PropertyNewValue
+            })?;
+            let (_, chunk) = self.take_property::<c_uchar>(target)?;
+            if chunk.is_empty() {
+                return Some(data);
+            }
+            data.extend_from_slice(&chunk);
+        }
+    }
+
+    // Called by the event loop for each input event, as ICCCM asks for selections to be taken and
+    // requested at the time of the event that led to it.
+    pub fn set_time(&self, time: ffi::Time) {
+        *self.time.lock() = time;
+    }
+
+    fn time(&self) -> ffi::Time {
+        *self.time.lock()
+    }
+
+    fn owned_contents(&self, selection: ffi::Atom) -> Option<Arc<OwnedContents>> {
+        self.contents.lock().get(&selection).cloned()
+    }
+
+    // Asks the owner of `selection` to convert it to `target`, and waits for the result to be
+    // stored in the transfer property.
+    fn convert(&self, selection: ffi::Atom, target: ffi::Atom) -> bool {
+        self.pending.lock().clear();
+        unsafe {
+            (self.xconn.xlib.XConvertSelection)(
+                self.xconn.display,
+                selection,
+                target,
+                self.atoms.transfer,
+                self.window,
+                self.time(),
+            );
+        }
+        if self.xconn.flush_requests().is_err() {
+            return false;
+        }
+
+        let notify = self.wait_for_event(ffi::SelectionNotify, |event| {
+            let event: &ffi::XSelectionEvent = event.as_ref();
+            event.selection == selection
+        });
+        match notify {
+            // A property of `None` means the owner couldn't convert the selection.
+            Some(notify) => {
+                let notify: &ffi::XSelectionEvent = notify.as_ref();
+                notify.property != 0
+            },
+            None => false,
+        }
+    }
+
+    // Reads and deletes the transfer property, accepting whatever type the owner gave it.
+    //
+    // For `INCR`, this returns no data, and deleting the property starts the transfer.
+    fn take_property<T: util::Formattable>(
+        &self,
+        expected_type: ffi::Atom,
+    ) -> Option<(ffi::Atom, Vec<T>)> {
+        let result = match self.xconn.get_property::<T>(self.window, self.atoms.transfer, expected_type) {
+            Err(ref e) if e.is_actual_property_type(self.atoms.incr) => Ok((self.atoms.incr, Vec::new())),
+            Err(util::GetPropertyError::TypeMismatch(actual_type)) if actual_type != 0 => {
+                self.xconn
+                    .get_property::<T>(self.window, self.atoms.transfer, actual_type)
+                    .map(|data| (actual_type, data))
+            },
+            result => result.map(|data| (expected_type, data)),
+        };
+        unsafe {
+            (self.xconn.xlib.XDeleteProperty)(self.xconn.display, self.window, self.atoms.transfer);
+        }
+        self.xconn.flush_requests().ok()?;
+        result.ok()
+    }
+
+    fn wait_for_event<F>(&self, event_type: c_int, matches: F) -> Option<ffi::XEvent>
+    where
+        F: Fn(&ffi::XEvent) -> bool,
+    {
+        let deadline = Instant::now() + Duration::from_millis(CONVERSION_TIMEOUT_MS);
+        // Events that didn't match, which are put back for whoever is waiting for them.
+        let mut skipped = Vec::new();
+        let found = loop {
+            // The event loop isn't necessarily running while we wait (we might be called from
+            // within its callback), so we look for the event ourselves as well.
+            let event = unsafe {
+                let mut event: ffi::XEvent = mem::uninitialized();
+                let found = (self.xconn.xlib.XCheckTypedWindowEvent)(
+                    self.xconn.display,
+                    self.window,
+                    event_type,
+                    &mut event,
+                );
+                if found == ffi::True {
+                    Some(event)
+                } else {
+                    let mut pending = self.pending.lock();
+                    pending
+                        .iter()
+                        .position(|event| event.get_type() == event_type)
+                        .map(|idx| pending.remove(idx))
+                }
+            };
+            match event {
+                Some(event) if matches(&event) => break Some(event),
+                Some(event) => skipped.push(event),
+                None => {
+                    let now = Instant::now();
+                    if now >= deadline || !self.wait_for_input(deadline - now) {
+                        break None;
+                    }
+                }
+            }
+        };
+        self.pending.lock().extend(skipped);
+        found
+    }
+
+    // Blocks until the server sends something or the event loop adds to `pending`, returning
+    // false if that didn't happen within `timeout`.
+    fn wait_for_input(&self, timeout: Duration) -> bool {
+        let mut poll_fds = [
+            libc::pollfd {
+                fd: unsafe { (self.xconn.xlib.XConnectionNumber)(self.xconn.display) },
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: self.pending_pipe.0,
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+        // Rounded up, so we don't wake up just before the deadline.
+        let timeout_ms = timeout.as_secs() * 1000 + (timeout.subsec_nanos() as u64 + 999_999) / 1_000_000;
+        let ready = unsafe {
+            libc::poll(poll_fds.as_mut_ptr(), poll_fds.len() as libc::nfds_t, timeout_ms as c_int)
+        };
+        if poll_fds[1].revents != 0 {
+            let mut buf = [0u8; 64];
+            while unsafe { libc::read(self.pending_pipe.0, buf.as_mut_ptr() as *mut c_void, buf.len()) } > 0 {}
+        }
+        // Being interrupted by a signal isn't a timeout.
+        ready != 0
+    }
+
+    fn add_pending(&self, event: ffi::XEvent) {
+        self.pending.lock().push(event);
+        unsafe { libc::write(self.pending_pipe.1, [0u8].as_ptr() as *const c_void, 1) };
+    }
+
+    fn discard_events(&self, event_type: c_int) {
+        unsafe {
+            let mut event: ffi::XEvent = mem::uninitialized();
+            while (self.xconn.xlib.XCheckTypedWindowEvent)(
+                self.xconn.display,
+                self.window,
+                event_type,
+                &mut event,
+            ) == ffi::True {}
+        }
+        self.pending.lock().retain(|event| event.get_type() != event_type);
+    }
+
+    // Called by the event loop for `SelectionNotify` events sent to `self.window`.
+    pub fn handle_notify(&self, event: &ffi::XSelectionEvent) {
+        self.add_pending(ffi::XEvent::from(*event));
+    }
+
+    // Called by the event loop for all `PropertyNotify` events, since `INCR` transfers we send
+    // are driven by the requestor deleting properties on its own windows.
+    pub fn handle_property(&self, event: &ffi::XPropertyEvent) {
+        if event.window == self.window {
+            if event.atom == self.atoms.transfer && event.state == ffi::PropertyNewValue {
+                self.add_pending(ffi::XEvent::from(*event));
+            }
+            return;
+        }
+        if event.state != ffi::PropertyDelete {
+            return;
+        }
+
+        let mut transfers = self.incr_transfers.lock();
+        let idx = match transfers
+            .iter()
+            .position(|t| t.requestor == event.window && t.property == event.atom)
+        {
+            Some(idx) => idx,
+            None => return,
+        };
+        let done = {
+            let transfer = &mut transfers[idx];
+            let end = cmp::min(transfer.sent + self.max_chunk_size, transfer.data.len());
+            self.xconn.change_property(
+                transfer.requestor,
+                transfer.property,
+                transfer.target,
+                util::PropMode::Replace,
+                &transfer.data[transfer.sent..end],
+            ).queue();
+            let done = transfer.sent == end;
+            transfer.sent = end;
+            done
+        };
+        if done {
+            // That was the empty chunk marking the end.
+            let transfer = transfers.swap_remove(idx);
+            if transfers.iter().all(|t| t.requestor != transfer.requestor) {
+                unsafe {
+                    (self.xconn.xlib.XSelectInput)(self.xconn.display, transfer.requestor, transfer.event_mask);
+                }
+            }
+        }
+        let _ = self.xconn.flush_requests();
+    }
+
+    // Called by the event loop when another client wants the contents of one of our selections.
+    pub fn handle_request(&self, request: &ffi::XSelectionRequestEvent) {
+        // Obsolete clients don't specify a property.
+        let property = if request.property == 0 { request.target } else { request.property };
+
+        let converted = match self.owned_contents(request.selection) {
+            Some(contents) => {
+                if request.target == self.atoms.targets {
+                    let mut targets = vec![self.atoms.targets];
+                    targets.extend(contents.iter().map(|&(atom, _, _)| atom));
+                    self.xconn.change_property(
+                        request.requestor,
+                        property,
+                        ffi::XA_ATOM,
+                        util::PropMode::Replace,
+                        &targets[..],
+                    ).queue();
+                    true
+                } else if let Some(&(_, _, ref data)) =
+                    contents.iter().find(|&&(atom, _, _)| atom == request.target)
+                {
+                    self.send_data(request.requestor, property, request.target, data.get().into_owned())
+                } else {
+                    false
+                }
+            },
+            None => false,
+        };
+
+        let mut notify: ffi::XSelectionEvent = unsafe { mem::zeroed() };
+        notify.type_ = ffi::SelectionNotify;
+        notify.display = self.xconn.display;
+        notify.requestor = request.requestor;
+        notify.selection = request.selection;
+        notify.target = request.target;
+        notify.property = if converted { property } else { 0 };
+        notify.time = request.time;
+        let _ = self.xconn.send_event(request.requestor, None, notify).flush();
+    }
+
+    // Returns false if the requestor's window is gone.
+    fn send_data(&self, requestor: ffi::Window, property: ffi::Atom, target: ffi::Atom, data: Vec<u8>) -> bool {
+        if data.len() <= self.max_chunk_size {
+            self.xconn.change_property(
+                requestor,
+                property,
+                target,
+                util::PropMode::Replace,
+                &data[..],
+            ).queue();
+            return true;
+        }
+
+        // Too large for a single request, so the requestor will take it a chunk at a time,
+        // deleting the property each time it's ready for the next.
+        let mut transfers = self.incr_transfers.lock();
+        // Other transfers to the same window already added to its event mask.
+        let event_mask = match transfers.iter().find(|t| t.requestor == requestor) {
+            Some(transfer) => transfer.event_mask,
+            None => unsafe {
+                let mut attributes: ffi::XWindowAttributes = mem::uninitialized();
+                if (self.xconn.xlib.XGetWindowAttributes)(self.xconn.display, requestor, &mut attributes) == 0 {
+                    self.xconn.ignore_error();
+                    return false;
+                }
+                (self.xconn.xlib.XSelectInput)(
+                    self.xconn.display,
+                    requestor,
+                    attributes.your_event_mask | ffi::PropertyChangeMask,
+                );
+                attributes.your_event_mask
+            },
+        };
+        self.xconn.change_property(
+            requestor,
+            property,
+            self.atoms.incr,
+            util::PropMode::Replace,
+            &[data.len() as util::Cardinal],
+        ).queue();
+        // A new request for the same property replaces the one in progress.
+        transfers.retain(|t| !(t.requestor == requestor && t.property == property));
+        transfers.push(IncrTransfer { requestor, property, target, data, sent: 0, event_mask });
+        true
+    }
+
+    // Called by the event loop when another client took ownership of one of our selections.
+    pub fn handle_clear(&self, event: &ffi::XSelectionClearEvent) {
+        self.contents.lock().remove(&event.selection);
+    }
+}
+
+impl Drop for Clipboard {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.pending_pipe.0);
+            libc::close(self.pending_pipe.1);
+        }
+    }
+}
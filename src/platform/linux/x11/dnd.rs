@@ -1,12 +1,10 @@
-use std::io;
 use std::sync::Arc;
-use std::path::{Path, PathBuf};
-use std::str::Utf8Error;
+use std::path::PathBuf;
 use std::os::raw::*;
 
-use percent_encoding::percent_decode;
-
+use DndAction;
 use super::{ffi, util, XConnection, XError};
+use super::super::dnd::{parse_uri_list, DndDataParseError};
 
 #[derive(Debug)]
 pub struct DndAtoms {
@@ -17,6 +15,11 @@ pub struct DndAtoms {
     pub position: ffi::Atom,
     pub status: ffi::Atom,
     pub action_private: ffi::Atom,
+    pub action_copy: ffi::Atom,
+    pub action_move: ffi::Atom,
+    pub action_link: ffi::Atom,
+    pub action_ask: ffi::Atom,
+    pub action_list: ffi::Atom,
     pub selection: ffi::Atom,
     pub finished: ffi::Atom,
     pub type_list: ffi::Atom,
@@ -34,6 +37,11 @@ impl DndAtoms {
             b"XdndPosition\0".as_ptr() as *mut c_char,
             b"XdndStatus\0".as_ptr() as *mut c_char,
             b"XdndActionPrivate\0".as_ptr() as *mut c_char,
+            b"XdndActionCopy\0".as_ptr() as *mut c_char,
+            b"XdndActionMove\0".as_ptr() as *mut c_char,
+            b"XdndActionLink\0".as_ptr() as *mut c_char,
+            b"XdndActionAsk\0".as_ptr() as *mut c_char,
+            b"XdndActionList\0".as_ptr() as *mut c_char,
             b"XdndSelection\0".as_ptr() as *mut c_char,
             b"XdndFinished\0".as_ptr() as *mut c_char,
             b"XdndTypeList\0".as_ptr() as *mut c_char,
@@ -49,40 +57,47 @@ impl DndAtoms {
             position: atoms[4],
             status: atoms[5],
             action_private: atoms[6],
-            selection: atoms[7],
-            finished: atoms[8],
-            type_list: atoms[9],
-            uri_list: atoms[10],
-            none: atoms[11],
+            action_copy: atoms[7],
+            action_move: atoms[8],
+            action_link: atoms[9],
+            action_ask: atoms[10],
+            action_list: atoms[11],
+            selection: atoms[12],
+            finished: atoms[13],
+            type_list: atoms[14],
+            uri_list: atoms[15],
+            none: atoms[16],
         })
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum DndState {
-    Accepted,
-    Rejected,
-}
-
-#[derive(Debug)]
-pub enum DndDataParseError {
-    EmptyData,
-    InvalidUtf8(Utf8Error),
-    HostnameSpecified(String),
-    UnexpectedProtocol(String),
-    UnresolvablePath(io::Error),
-}
+impl DndAtoms {
+    pub fn action_to_atom(&self, action: DndAction) -> ffi::Atom {
+        match action {
+            DndAction::Copy => self.action_copy,
+            DndAction::Move => self.action_move,
+            DndAction::Link => self.action_link,
+        }
+    }
 
-impl From<Utf8Error> for DndDataParseError {
-    fn from(e: Utf8Error) -> Self {
-        DndDataParseError::InvalidUtf8(e)
+    pub fn atom_to_action(&self, atom: ffi::Atom) -> Option<DndAction> {
+        if atom == self.action_copy {
+            Some(DndAction::Copy)
+        } else if atom == self.action_move {
+            Some(DndAction::Move)
+        } else if atom == self.action_link {
+            Some(DndAction::Link)
+        } else {
+            None
+        }
     }
 }
 
-impl From<io::Error> for DndDataParseError {
-    fn from(e: io::Error) -> Self {
-        DndDataParseError::UnresolvablePath(e)
-    }
+#[derive(Debug, Clone, Copy)]
+pub enum DndState {
+    // Along with the action we'll perform.
+    Accepted(ffi::Atom),
+    Rejected,
 }
 
 pub struct Dnd {
@@ -93,8 +108,14 @@ pub struct Dnd {
     pub type_list: Option<Vec<c_ulong>>,
     // Populated by XdndPosition event handler
     pub source_window: Option<c_ulong>,
+    // The first of the types accepted by the window that the source offers, if any
+    pub data_type: Option<ffi::Atom>,
+    // The action we last told the source we'd perform, or `None` if we rejected the drop
+    pub accepted_action: Option<ffi::Atom>,
     // Populated by SelectionNotify event handler (triggered by XdndPosition event handler)
     pub result: Option<Result<Vec<PathBuf>, DndDataParseError>>,
+    // Set by XdndDrop event handler when we've asked for `data_type`
+    pub awaiting_data: bool,
 }
 
 impl Dnd {
@@ -106,7 +127,10 @@ impl Dnd {
             version: None,
             type_list: None,
             source_window: None,
+            data_type: None,
+            accepted_action: None,
             result: None,
+            awaiting_data: false,
         })
     }
 
@@ -114,7 +138,10 @@ impl Dnd {
         self.version = None;
         self.type_list = None;
         self.source_window = None;
+        self.data_type = None;
+        self.accepted_action = None;
         self.result = None;
+        self.awaiting_data = false;
     }
 
     pub unsafe fn send_status(
@@ -122,17 +149,28 @@ impl Dnd {
         this_window: c_ulong,
         target_window: c_ulong,
         state: DndState,
+        area: Option<(c_short, c_short, c_ushort, c_ushort)>,
     ) -> Result<(), XError> {
         let (accepted, action) = match state {
-            DndState::Accepted => (1, self.atoms.action_private as c_long),
+            DndState::Accepted(action) => (1, action as c_long),
             DndState::Rejected => (0, self.atoms.none as c_long),
         };
+        // Without a rectangle (in root coordinates) where the answer holds, we want a position
+        // message for every movement.
+        let (flags, position, size) = match area {
+            Some((x, y, width, height)) => (
+                accepted,
+                ((x as c_long) << 16) | (y as c_long & 0xffff),
+                ((width as c_long) << 16) | (height as c_long),
+            ),
+            None => (accepted | 2, 0, 0),
+        };
         self.xconn.send_client_msg(
             target_window,
             target_window,
             self.atoms.status,
             None,
-            [this_window as c_long, accepted, 0, 0, action],
+            [this_window as c_long, flags, position, size, action],
         ).flush()
     }
 
@@ -143,7 +181,7 @@ impl Dnd {
         state: DndState,
     ) -> Result<(), XError> {
         let (accepted, action) = match state {
-            DndState::Accepted => (1, self.atoms.action_private as c_long),
+            DndState::Accepted(action) => (1, action as c_long),
             DndState::Rejected => (0, self.atoms.none as c_long),
         };
         self.xconn.send_client_msg(
@@ -166,11 +204,11 @@ impl Dnd {
         )
     }
 
-    pub unsafe fn convert_selection(&self, window: c_ulong, time: c_ulong) {
+    pub unsafe fn convert_selection(&self, window: c_ulong, target: ffi::Atom, time: c_ulong) {
         (self.xconn.xlib.XConvertSelection)(
             self.xconn.display,
             self.atoms.selection,
-            self.atoms.uri_list,
+            target,
             self.atoms.selection,
             window,
             time,
@@ -180,40 +218,18 @@ impl Dnd {
     pub unsafe fn read_data(
         &self,
         window: c_ulong,
+        target: ffi::Atom,
     ) -> Result<Vec<c_uchar>, util::GetPropertyError> {
-        self.xconn.get_property(
-            window,
-            self.atoms.selection,
-            self.atoms.uri_list,
-        )
+        match self.xconn.get_property(window, self.atoms.selection, target) {
+            // Sources don't always label the data with the target it was converted to.
+            Err(util::GetPropertyError::TypeMismatch(actual_type)) if actual_type != 0 => {
+                self.xconn.get_property(window, self.atoms.selection, actual_type)
+            },
+            result => result,
+        }
     }
 
     pub fn parse_data(&self, data: &mut Vec<c_uchar>) -> Result<Vec<PathBuf>, DndDataParseError> {
-        if !data.is_empty() {
-            let mut path_list = Vec::new();
-            let decoded = percent_decode(data).decode_utf8()?.into_owned();
-            for uri in decoded.split("\r\n").filter(|u| !u.is_empty()) {
-                // The format is specified as protocol://host/path
-                // However, it's typically simply protocol:///path
-                let path_str = if uri.starts_with("file://") {
-                    let path_str = uri.replace("file://", "");
-                    if !path_str.starts_with('/') {
-                        // A hostname is specified
-                        // Supporting this case is beyond the scope of my mental health
-                        return Err(DndDataParseError::HostnameSpecified(path_str));
-                    }
-                    path_str
-                } else {
-                    // Only the file protocol is supported
-                    return Err(DndDataParseError::UnexpectedProtocol(uri.to_owned()));
-                };
-
-                let path = Path::new(&path_str).canonicalize()?;
-                path_list.push(path);
-            }
-            Ok(path_list)
-        } else {
-            Err(DndDataParseError::EmptyData)
-        }
+        parse_uri_list(data)
     }
 }
@@ -0,0 +1,341 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::os::raw::*;
+
+use parking_lot::Mutex;
+
+use DndAction;
+use super::{ffi, util, XConnection, XError};
+use super::dnd::DndAtoms;
+
+// The highest version of XDnD we speak as a source.
+const XDND_VERSION: c_long = 5;
+
+// How long we wait for the target to answer a drop with XdndFinished before giving up on it.
+const FINISHED_TIMEOUT_MS: u64 = 5000;
+
+// The XdndAware window under the pointer, along with what it last told us.
+struct Target {
+    window: ffi::Window,
+    version: c_long,
+    accepted: bool,
+    action: ffi::Atom,
+    // We must not send another XdndPosition until the last one has been answered.
+    awaiting_status: bool,
+}
+
+struct ActiveDrag {
+    source: ffi::Window,
+    root: ffi::Window,
+    types: Vec<ffi::Atom>,
+    // The action we ask targets for; the first one the application allows.
+    action: ffi::Atom,
+    target: Option<Target>,
+    // The position to send once the target has answered the previous one.
+    pending_position: Option<(c_int, c_int, ffi::Time)>,
+    // When we give up on the target answering with XdndFinished, once XdndDrop has been sent.
+    dropped: Option<Instant>,
+}
+
+// Our side of drags started with `start_drag`, shared between the event loop and the windows.
+pub struct DragSource {
+    xconn: Arc<XConnection>,
+    pub atoms: DndAtoms,
+    drag: Mutex<Option<ActiveDrag>>,
+}
+
+impl DragSource {
+    pub fn new(xconn: Arc<XConnection>) -> Result<Self, XError> {
+        let atoms = DndAtoms::new(&xconn)?;
+        Ok(DragSource { xconn, atoms, drag: Mutex::new(None) })
+    }
+
+    // Starts a drag from `source`, which must already own `XdndSelection`.
+    //
+    // The pointer is grabbed until the button is released, so the event loop gets the motion.
+    pub fn start(
+        &self,
+        source: ffi::Window,
+        root: ffi::Window,
+        types: Vec<ffi::Atom>,
+        actions: &[DndAction],
+    ) -> Result<(), String> {
+        let actions: Vec<ffi::Atom> = actions
+            .iter()
+            .map(|&action| self.atoms.action_to_atom(action))
+            .collect();
+        let action = actions.first().cloned().unwrap_or(self.atoms.action_copy);
+
+        // Targets only read the list when there are more types than fit in XdndEnter.
+        self.xconn.change_property(
+            source,
+            self.atoms.type_list,
+            ffi::XA_ATOM,
+            util::PropMode::Replace,
+            &types[..],
+        ).queue();
+        self.xconn.change_property(
+            source,
+            self.atoms.action_list,
+            ffi::XA_ATOM,
+            util::PropMode::Replace,
+            &actions[..],
+        ).queue();
+
+        let grab_result = unsafe {
+            (self.xconn.xlib.XGrabPointer)(
+                self.xconn.display,
+                source,
+                ffi::False,
+                (ffi::ButtonReleaseMask | ffi::PointerMotionMask) as c_uint,
+                ffi::GrabModeAsync,
+                ffi::GrabModeAsync,
+                0,
+                0,
+                ffi::CurrentTime,
+            )
+        };
+        self.xconn
+            .check_errors()
+            .map_err(|err| format!("Drag could not be started: {:?}", err))?;
+        match grab_result {
+            ffi::GrabSuccess => Ok(()),
+            ffi::AlreadyGrabbed => Err("Drag could not be started: cursor already grabbed by another client"),
+            ffi::GrabInvalidTime => Err("Drag could not be started: invalid time"),
+            ffi::GrabNotViewable => Err("Drag could not be started: window not viewable"),
+            ffi::GrabFrozen => Err("Drag could not be started: cursor frozen by another client"),
+            _ => unreachable!(),
+        }.map_err(|err| err.to_owned())?;
+
+        *self.drag.lock() = Some(ActiveDrag {
+            source,
+            root,
+            types,
+            action,
+            target: None,
+            pending_position: None,
+            dropped: None,
+        });
+        Ok(())
+    }
+
+    pub fn handle_motion(&self, x_root: c_int, y_root: c_int, time: ffi::Time) {
+        let mut drag = self.drag.lock();
+        let drag = match *drag {
+            Some(ref mut drag) if drag.dropped.is_none() => drag,
+            _ => return,
+        };
+
+        let new_target = self.find_target(drag.root, x_root, y_root);
+        let target_changed = match (&drag.target, &new_target) {
+            (&Some(ref target), &Some((window, _))) => target.window != window,
+            (&None, &None) => false,
+            _ => true,
+        };
+        if target_changed {
+            if let Some(target) = drag.target.take() {
+                self.send(target.window, self.atoms.leave, [drag.source as c_long, 0, 0, 0, 0]);
+            }
+            if let Some((window, version)) = new_target {
+                let version = version.min(XDND_VERSION);
+                let more_types = if drag.types.len() > 3 { 1 } else { 0 };
+                let ty = |i: usize| drag.types.get(i).cloned().unwrap_or(0) as c_long;
+                self.send(
+                    window,
+                    self.atoms.enter,
+                    [drag.source as c_long, (version << 24) | more_types, ty(0), ty(1), ty(2)],
+                );
+                drag.target = Some(Target {
+                    window,
+                    version,
+                    accepted: false,
+                    action: self.atoms.none,
+                    awaiting_status: false,
+                });
+            }
+            drag.pending_position = None;
+        }
+
+        drag.pending_position = Some((x_root, y_root, time));
+        self.send_pending_position(drag);
+    }
+
+    fn send_pending_position(&self, drag: &mut ActiveDrag) {
+        let target = match drag.target {
+            Some(ref mut target) if !target.awaiting_status => target,
+            _ => return,
+        };
+        if let Some((x_root, y_root, time)) = drag.pending_position.take() {
+            let coordinates = ((x_root as c_long) << 16) | (y_root as c_long & 0xffff);
+            self.send(
+                target.window,
+                self.atoms.position,
+                [drag.source as c_long, 0, coordinates, time as c_long, drag.action as c_long],
+            );
+            target.awaiting_status = true;
+        }
+    }
+
+    pub fn handle_status(&self, client_msg: &ffi::XClientMessageEvent) {
+        let mut drag = self.drag.lock();
+        let drag = match *drag {
+            Some(ref mut drag) => drag,
+            None => return,
+        };
+        {
+            let target = match drag.target {
+                Some(ref mut target) if target.window == client_msg.data.get_long(0) as ffi::Window => target,
+                _ => return,
+            };
+            target.accepted = client_msg.data.get_long(1) & 1 == 1;
+            target.action = if target.version >= 2 {
+                client_msg.data.get_long(4) as ffi::Atom
+            } else {
+                self.atoms.action_copy
+            };
+            target.awaiting_status = false;
+        }
+        if drag.dropped.is_none() {
+            self.send_pending_position(drag);
+        }
+    }
+
+    // Returns the source window and the result if the drag ended without a drop.
+    pub fn handle_release(&self, time: ffi::Time) -> Option<(ffi::Window, Option<DndAction>)> {
+        let mut drag_lock = self.drag.lock();
+        let ended = {
+            let drag = match *drag_lock {
+                Some(ref mut drag) if drag.dropped.is_none() => drag,
+                _ => return None,
+            };
+            unsafe {
+                (self.xconn.xlib.XUngrabPointer)(self.xconn.display, ffi::CurrentTime);
+            }
+            match drag.target {
+                Some(ref target) if target.accepted => {
+                    self.send(target.window, self.atoms.drop, [drag.source as c_long, 0, time as c_long, 0, 0]);
+                    drag.dropped = Some(Instant::now() + Duration::from_millis(FINISHED_TIMEOUT_MS));
+                    None
+                },
+                Some(ref target) => {
+                    self.send(target.window, self.atoms.leave, [drag.source as c_long, 0, 0, 0, 0]);
+                    Some((drag.source, None))
+                },
+                None => Some((drag.source, None)),
+            }
+        };
+        let _ = self.xconn.flush_requests();
+        if ended.is_some() {
+            *drag_lock = None;
+        }
+        ended
+    }
+
+    // Returns the source window and the action the target performed.
+    pub fn handle_finished(
+        &self,
+        client_msg: &ffi::XClientMessageEvent,
+    ) -> Option<(ffi::Window, Option<DndAction>)> {
+        let mut drag_lock = self.drag.lock();
+        let result = {
+            let drag = match *drag_lock {
+                Some(ref drag) if drag.dropped.is_some() => drag,
+                _ => return None,
+            };
+            let target = match drag.target {
+                Some(ref target) if target.window == client_msg.data.get_long(0) as ffi::Window => target,
+                _ => return None,
+            };
+            let action = if target.version >= 5 {
+                if client_msg.data.get_long(1) & 1 == 1 {
+                    Some(client_msg.data.get_long(2) as ffi::Atom)
+                } else {
+                    None
+                }
+            } else {
+                Some(target.action)
+            };
+            // Targets that don't say which action they performed did the one we asked for.
+            let action = action.map(|action| {
+                self.atoms.atom_to_action(action)
+                    .or_else(|| self.atoms.atom_to_action(drag.action))
+                    .unwrap_or(DndAction::Copy)
+            });
+            (drag.source, action)
+        };
+        *drag_lock = None;
+        Some(result)
+    }
+
+    // How long the event loop can wait before the drop has to be given up on, if there is one.
+    pub fn timeout(&self) -> Option<Duration> {
+        let deadline = match self.drag.lock().as_ref().and_then(|drag| drag.dropped) {
+            Some(deadline) => deadline,
+            None => return None,
+        };
+        let now = Instant::now();
+        Some(if deadline > now { deadline - now } else { Duration::from_millis(0) })
+    }
+
+    // Returns the source window if the drop went unanswered for too long.
+    pub fn check_timeout(&self) -> Option<ffi::Window> {
+        let mut drag_lock = self.drag.lock();
+        let source = match *drag_lock {
+            Some(ActiveDrag { source, dropped: Some(deadline), .. }) if deadline <= Instant::now() => source,
+            _ => return None,
+        };
+        *drag_lock = None;
+        Some(source)
+    }
+
+    // Finds the XdndAware window under the given root coordinates, and its XDnD version.
+    fn find_target(&self, root: ffi::Window, x_root: c_int, y_root: c_int) -> Option<(ffi::Window, c_long)> {
+        let mut window = root;
+        loop {
+            if let Some(version) = self.xdnd_version(window) {
+                return Some((window, version));
+            }
+            let mut child = 0;
+            unsafe {
+                let (mut x, mut y) = (0, 0);
+                (self.xconn.xlib.XTranslateCoordinates)(
+                    self.xconn.display,
+                    root,
+                    window,
+                    x_root,
+                    y_root,
+                    &mut x,
+                    &mut y,
+                    &mut child,
+                );
+            }
+            if child == 0 || self.xconn.check_errors().is_err() {
+                return None;
+            }
+            window = child;
+        }
+    }
+
+    fn xdnd_version(&self, window: ffi::Window) -> Option<c_long> {
+        self.xconn
+            .get_property::<c_ulong>(window, self.atoms.aware, ffi::XA_ATOM)
+            .ok()
+            .and_then(|version| version.first().cloned())
+            .map(|version| version as c_long)
+    }
+
+    fn send(&self, target: ffi::Window, message_type: ffi::Atom, data: util::ClientMsgPayload) {
+        self.xconn.send_client_msg(target, target, message_type, None, data).queue();
+    }
+}
+
+impl Drop for DragSource {
+    fn drop(&mut self) {
+        if self.drag.get_mut().is_some() {
+            unsafe {
+                (self.xconn.xlib.XUngrabPointer)(self.xconn.display, ffi::CurrentTime);
+            }
+        }
+    }
+}
+
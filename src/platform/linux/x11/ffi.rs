@@ -6,3 +6,87 @@ pub use x11_dl::xinput2::*;
 pub use x11_dl::xlib_xcb::*;
 pub use x11_dl::error::OpenError;
 pub use x11_dl::xrandr::*;
+
+use std::os::raw::{c_double, c_int, c_ulong};
+
+// The gesture events of XInput 2.4, which x11-dl doesn't have yet.
+
+#[allow(non_upper_case_globals)]
+pub const XI_GesturePinchBegin: c_int = 27;
+#[allow(non_upper_case_globals)]
+pub const XI_GesturePinchUpdate: c_int = 28;
+#[allow(non_upper_case_globals)]
+pub const XI_GesturePinchEnd: c_int = 29;
+#[allow(non_upper_case_globals)]
+pub const XI_GestureSwipeBegin: c_int = 30;
+#[allow(non_upper_case_globals)]
+pub const XI_GestureSwipeUpdate: c_int = 31;
+#[allow(non_upper_case_globals)]
+pub const XI_GestureSwipeEnd: c_int = 32;
+
+#[allow(non_upper_case_globals)]
+pub const XIGesturePinchEventCancelled: c_int = 1 << 0;
+#[allow(non_upper_case_globals)]
+pub const XIGestureSwipeEventCancelled: c_int = 1 << 0;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct XIGesturePinchEvent {
+    pub _type: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub extension: c_int,
+    pub evtype: c_int,
+    pub time: Time,
+    pub deviceid: c_int,
+    pub sourceid: c_int,
+    pub detail: c_int,
+    pub root: Window,
+    pub event: Window,
+    pub child: Window,
+    pub root_x: c_double,
+    pub root_y: c_double,
+    pub event_x: c_double,
+    pub event_y: c_double,
+    pub delta_x: c_double,
+    pub delta_y: c_double,
+    pub delta_unaccel_x: c_double,
+    pub delta_unaccel_y: c_double,
+    pub scale: c_double,
+    pub delta_angle: c_double,
+    pub flags: c_int,
+    pub mods: XIModifierState,
+    pub group: XIGroupState,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct XIGestureSwipeEvent {
+    pub _type: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub extension: c_int,
+    pub evtype: c_int,
+    pub time: Time,
+    pub deviceid: c_int,
+    pub sourceid: c_int,
+    pub detail: c_int,
+    pub root: Window,
+    pub event: Window,
+    pub child: Window,
+    pub root_x: c_double,
+    pub root_y: c_double,
+    pub event_x: c_double,
+    pub event_y: c_double,
+    pub delta_x: c_double,
+    pub delta_y: c_double,
+    pub delta_unaccel_x: c_double,
+    pub delta_unaccel_y: c_double,
+    pub flags: c_int,
+    pub mods: XIModifierState,
+    pub group: XIGroupState,
+}
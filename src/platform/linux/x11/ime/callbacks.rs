@@ -7,7 +7,7 @@ use super::{ffi, XConnection, XError};
 
 use super::inner::{close_im, ImeInner};
 use super::input_method::PotentialInputMethods;
-use super::context::{ImeContextCreationError, ImeContext};
+use super::context::{ImeContextCreationError, ImeContext, Style};
 
 pub unsafe fn xim_set_callback(
     xconn: &Arc<XConnection>,
@@ -109,21 +109,35 @@ unsafe fn replace_im(inner: *mut ImeInner) -> Result<(), ReplaceImError> {
         result
     }.map_err(ReplaceImError::SetDestroyCallbackFailed)?;
 
+    let new_style = Style::preferred(xconn, new_im.im);
+    let new_none_supported = Style::None.is_supported(xconn, new_im.im);
+
     let mut new_contexts = HashMap::new();
     for (window, old_context) in (*inner).contexts.iter() {
         let spot = old_context.as_ref().map(|old_context| old_context.ic_spot);
-        let new_context = {
+        // Windows that disallowed the IME keep doing so, even if that happened while it was gone.
+        let style = if (*inner).disallowed.contains(window) && new_none_supported {
+            Style::None
+        } else {
+            new_style
+        };
+        let mut new_context = {
             let result = ImeContext::new(
                 xconn,
                 new_im.im,
+                style,
                 *window,
                 spot,
+                (*inner).event_sender.clone(),
             );
             if result.is_err() {
                 let _ = close_im(xconn, new_im.im);
             }
             result.map_err(ReplaceImError::ContextCreationFailed)?
         };
+        if let Some(&ImeContext { ic_area: Some(area), .. }) = old_context.as_ref() {
+            new_context.set_area(xconn, area.x, area.y, area.width, area.height);
+        }
         new_contexts.insert(*window, Some(new_context));
     }
 
@@ -131,6 +145,8 @@ unsafe fn replace_im(inner: *mut ImeInner) -> Result<(), ReplaceImError> {
     let _ = (*inner).destroy_all_contexts_if_necessary();
     let _ = (*inner).close_im_if_necessary();
     (*inner).im = new_im.im;
+    (*inner).style = new_style;
+    (*inner).none_supported = new_none_supported;
     (*inner).contexts = new_contexts;
     (*inner).is_destroyed = false;
     (*inner).is_fallback = is_fallback;
@@ -1,8 +1,11 @@
-use std::ptr;
+use std::{mem, ptr, slice};
+use std::ffi::CStr;
 use std::sync::Arc;
-use std::os::raw::{c_short, c_void};
+use std::os::raw::{c_short, c_ushort, c_ulong, c_void};
 
-use super::{ffi, util, XConnection, XError};
+use libc;
+
+use super::{ffi, util, ImeEvent, ImeEventSender, XConnection, XError};
 
 #[derive(Debug)]
 pub enum ImeContextCreationError {
@@ -10,6 +13,239 @@ pub enum ImeContextCreationError {
     Null,
 }
 
+// Not provided by `x11-dl`.
+#[repr(C)]
+struct XIMStyles {
+    count_styles: c_ushort,
+    supported_styles: *const c_ulong,
+}
+
+/// The pre-edit style used by an input context.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Style {
+    /// The input method reports the pre-edit text to us, and we forward it to the application.
+    Callbacks,
+    /// The input method draws the pre-edit text in a window of its own.
+    Nothing,
+    /// The input method is bypassed, so key presses are only translated through the keymap.
+    None,
+}
+
+impl Style {
+    // Picks `Callbacks` when the input method supports it, and otherwise falls back to letting
+    // the input method draw its own pre-edit window.
+    pub unsafe fn preferred(xconn: &Arc<XConnection>, im: ffi::XIM) -> Style {
+        if Style::Callbacks.is_supported(xconn, im) {
+            Style::Callbacks
+        } else {
+            Style::Nothing
+        }
+    }
+
+    pub unsafe fn is_supported(&self, xconn: &Arc<XConnection>, im: ffi::XIM) -> bool {
+        let mut styles: *mut XIMStyles = ptr::null_mut();
+        let result = (xconn.xlib.XGetIMValues)(
+            im,
+            ffi::XNQueryInputStyle_0.as_ptr() as *const _,
+            &mut styles,
+            ptr::null_mut::<()>(),
+        );
+        if !result.is_null() || styles.is_null() {
+            let _ = xconn.check_errors();
+            return false;
+        }
+
+        let supported = slice::from_raw_parts(
+            (*styles).supported_styles,
+            (*styles).count_styles as usize,
+        ).contains(&self.input_style());
+        (xconn.xlib.XFree)(styles as _);
+        supported
+    }
+
+    fn input_style(&self) -> c_ulong {
+        let style = match *self {
+            Style::Callbacks => ffi::XIMPreeditCallbacks | ffi::XIMStatusNothing,
+            Style::Nothing => ffi::XIMPreeditNothing | ffi::XIMStatusNothing,
+            Style::None => ffi::XIMPreeditNone | ffi::XIMStatusNone,
+        };
+        style as _
+    }
+}
+
+// The pre-edit callbacks all receive a pointer to this, so it lives in a `Box` owned by the
+// context and never moves.
+struct PreeditState {
+    window: ffi::Window,
+    event_sender: ImeEventSender,
+    // The pre-edit text, as the input method edits it by character index.
+    text: Vec<char>,
+    cursor_pos: usize,
+}
+
+impl PreeditState {
+    fn send(&self) {
+        let text: String = self.text.iter().collect();
+        let cursor = self.text.iter().take(self.cursor_pos).map(|c| c.len_utf8()).sum();
+        let _ = self.event_sender.send((self.window, ImeEvent::Update(text, cursor)));
+    }
+}
+
+unsafe fn xim_text_to_chars(text: &ffi::XIMText) -> Vec<char> {
+    if text.encoding_is_wchar == ffi::True {
+        // `XIMText::string` is a union of two pointers, and x11-dl only declares the other one as
+        // a pointer.
+        let wide = text.string.multi_byte as *const libc::wchar_t;
+        if wide.is_null() {
+            return Vec::new();
+        }
+        slice::from_raw_parts(wide, text.length as usize)
+            .iter()
+            .filter_map(|&c| ::std::char::from_u32(c as u32))
+            .collect()
+    } else {
+        let multi_byte = text.string.multi_byte;
+        if multi_byte.is_null() {
+            return Vec::new();
+        }
+        // We set the locale from the environment, which is UTF-8 in every sane configuration.
+        CStr::from_ptr(multi_byte).to_string_lossy().chars().collect()
+    }
+}
+
+unsafe extern fn preedit_start_callback(
+    _xim: ffi::XIM,
+    client_data: ffi::XPointer,
+    _call_data: ffi::XPointer,
+) -> i32 {
+    let state = &mut *(client_data as *mut PreeditState);
+    state.text.clear();
+    state.cursor_pos = 0;
+    let _ = state.event_sender.send((state.window, ImeEvent::Start));
+    // No length limit on the pre-edit string.
+    -1
+}
+
+unsafe extern fn preedit_done_callback(
+    _xim: ffi::XIM,
+    client_data: ffi::XPointer,
+    _call_data: ffi::XPointer,
+) {
+    let state = &mut *(client_data as *mut PreeditState);
+    state.text.clear();
+    state.cursor_pos = 0;
+    let _ = state.event_sender.send((state.window, ImeEvent::End));
+}
+
+unsafe extern fn preedit_draw_callback(
+    _xim: ffi::XIM,
+    client_data: ffi::XPointer,
+    call_data: ffi::XPointer,
+) {
+    let state = &mut *(client_data as *mut PreeditState);
+    let call_data = &*(call_data as *const ffi::XIMPreeditDrawCallbackStruct);
+
+    let chg_first = (call_data.chg_first.max(0) as usize).min(state.text.len());
+    let chg_end = (chg_first + call_data.chg_length.max(0) as usize).min(state.text.len());
+    let new_chars = if call_data.text.is_null() {
+        Vec::new()
+    } else {
+        xim_text_to_chars(&*call_data.text)
+    };
+    state.text.splice(chg_first..chg_end, new_chars);
+    state.cursor_pos = (call_data.caret.max(0) as usize).min(state.text.len());
+    state.send();
+}
+
+unsafe extern fn preedit_caret_callback(
+    _xim: ffi::XIM,
+    client_data: ffi::XPointer,
+    call_data: ffi::XPointer,
+) {
+    let state = &mut *(client_data as *mut PreeditState);
+    let call_data = &mut *(call_data as *mut ffi::XIMPreeditCaretCallbackStruct);
+
+    let len = state.text.len();
+    let cursor_pos = state.cursor_pos;
+    // The pre-edit text is a single line, so moving up or down goes to its start or end.
+    let new_pos = match call_data.direction {
+        ffi::XIMCaretDirection::XIMAbsolutePosition => call_data.position.max(0) as usize,
+        ffi::XIMCaretDirection::XIMForwardChar => cursor_pos + 1,
+        ffi::XIMCaretDirection::XIMBackwardChar => cursor_pos.saturating_sub(1),
+        ffi::XIMCaretDirection::XIMForwardWord => next_word_boundary(&state.text, cursor_pos),
+        ffi::XIMCaretDirection::XIMBackwardWord => previous_word_boundary(&state.text, cursor_pos),
+        ffi::XIMCaretDirection::XIMLineStart
+        | ffi::XIMCaretDirection::XIMCaretUp
+        | ffi::XIMCaretDirection::XIMPreviousLine => 0,
+        ffi::XIMCaretDirection::XIMLineEnd
+        | ffi::XIMCaretDirection::XIMCaretDown
+        | ffi::XIMCaretDirection::XIMNextLine => len,
+        ffi::XIMCaretDirection::XIMDontChange => cursor_pos,
+    };
+    let new_pos = new_pos.min(len);
+    if new_pos != cursor_pos {
+        state.cursor_pos = new_pos;
+        state.send();
+    }
+    // The input method expects the position we settled on to be written back.
+    call_data.position = state.cursor_pos as _;
+}
+
+// The start of the next word after `pos`.
+fn next_word_boundary(text: &[char], pos: usize) -> usize {
+    let mut pos = pos;
+    while pos < text.len() && !text[pos].is_whitespace() {
+        pos += 1;
+    }
+    while pos < text.len() && text[pos].is_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+// The start of the word before `pos`.
+fn previous_word_boundary(text: &[char], pos: usize) -> usize {
+    let mut pos = pos.min(text.len());
+    while pos > 0 && text[pos - 1].is_whitespace() {
+        pos -= 1;
+    }
+    while pos > 0 && !text[pos - 1].is_whitespace() {
+        pos -= 1;
+    }
+    pos
+}
+
+// Keeps the callback structs alive for as long as the input context that refers to them.
+struct PreeditCallbacks {
+    start: ffi::XIMCallback,
+    done: ffi::XIMCallback,
+    draw: ffi::XIMCallback,
+    caret: ffi::XIMCallback,
+}
+
+impl PreeditCallbacks {
+    fn new(client_data: ffi::XPointer) -> PreeditCallbacks {
+        // `XIMProc` is declared without a return value, but the start callback returns the
+        // maximum pre-edit length.
+        let start_callback: unsafe extern fn(ffi::XIM, ffi::XPointer, ffi::XPointer) -> i32 =
+            preedit_start_callback;
+        PreeditCallbacks {
+            start: ffi::XIMCallback {
+                client_data,
+                callback: Some(unsafe { mem::transmute(start_callback) }),
+            },
+            done: ffi::XIMCallback { client_data, callback: Some(preedit_done_callback) },
+            draw: ffi::XIMCallback { client_data, callback: Some(preedit_draw_callback) },
+            caret: ffi::XIMCallback { client_data, callback: Some(preedit_caret_callback) },
+        }
+    }
+}
+
+struct ImeContextClientData {
+    _state: Box<PreeditState>,
+    callbacks: PreeditCallbacks,
+}
+
 unsafe fn create_pre_edit_attr<'a>(
     xconn: &'a Arc<XConnection>,
     ic_spot: &'a ffi::XPoint,
@@ -25,27 +261,101 @@ unsafe fn create_pre_edit_attr<'a>(
     ).expect("XVaCreateNestedList returned NULL")
 }
 
+unsafe fn create_pre_edit_attr_with_area<'a>(
+    xconn: &'a Arc<XConnection>,
+    ic_spot: &'a ffi::XPoint,
+    ic_area: &'a ffi::XRectangle,
+) -> util::XSmartPointer<'a, c_void> {
+    util::XSmartPointer::new(
+        xconn,
+        (xconn.xlib.XVaCreateNestedList)(
+            0,
+            ffi::XNSpotLocation_0.as_ptr() as *const _,
+            ic_spot,
+            ffi::XNArea_0.as_ptr() as *const _,
+            ic_area,
+            ptr::null_mut::<()>(),
+        ),
+    ).expect("XVaCreateNestedList returned NULL")
+}
+
+unsafe fn create_pre_edit_attr_with_callbacks<'a>(
+    xconn: &'a Arc<XConnection>,
+    ic_spot: &'a ffi::XPoint,
+    callbacks: &'a PreeditCallbacks,
+) -> util::XSmartPointer<'a, c_void> {
+    util::XSmartPointer::new(
+        xconn,
+        (xconn.xlib.XVaCreateNestedList)(
+            0,
+            ffi::XNSpotLocation_0.as_ptr() as *const _,
+            ic_spot,
+            ffi::XNPreeditStartCallback_0.as_ptr() as *const _,
+            &callbacks.start,
+            ffi::XNPreeditDoneCallback_0.as_ptr() as *const _,
+            &callbacks.done,
+            ffi::XNPreeditDrawCallback_0.as_ptr() as *const _,
+            &callbacks.draw,
+            ffi::XNPreeditCaretCallback_0.as_ptr() as *const _,
+            &callbacks.caret,
+            ptr::null_mut::<()>(),
+        ),
+    ).expect("XVaCreateNestedList returned NULL")
+}
+
 // WARNING: this struct doesn't destroy its XIC resource when dropped.
 // This is intentional, as it doesn't have enough information to know whether or not the context
 // still exists on the server. Since `ImeInner` has that awareness, destruction must be handled
 // through `ImeInner`.
-#[derive(Debug)]
 pub struct ImeContext {
     pub ic: ffi::XIC,
     pub ic_spot: ffi::XPoint,
+    // The cursor area, when one was given instead of just a spot.
+    pub ic_area: Option<ffi::XRectangle>,
+    pub style: Style,
+    pub is_focused: bool,
+    // Only present for `Style::Callbacks`. Must outlive `ic`.
+    _client_data: Option<ImeContextClientData>,
 }
 
 impl ImeContext {
     pub unsafe fn new(
         xconn: &Arc<XConnection>,
         im: ffi::XIM,
+        style: Style,
         window: ffi::Window,
         ic_spot: Option<ffi::XPoint>,
+        event_sender: ImeEventSender,
     ) -> Result<Self, ImeContextCreationError> {
-        let ic = if let Some(ic_spot) = ic_spot {
-            ImeContext::create_ic_with_spot(xconn, im, window, ic_spot)
-        } else {
-            ImeContext::create_ic(xconn, im, window)
+        let client_data = match style {
+            Style::Callbacks => {
+                let mut state = Box::new(PreeditState {
+                    window,
+                    event_sender,
+                    text: Vec::new(),
+                    cursor_pos: 0,
+                });
+                let callbacks = PreeditCallbacks::new(&mut *state as *mut PreeditState as _);
+                Some(ImeContextClientData { _state: state, callbacks })
+            },
+            Style::Nothing | Style::None => None,
+        };
+
+        let ic = match (ic_spot, client_data.as_ref()) {
+            (None, None) => ImeContext::create_ic(xconn, im, style, window, None),
+            // There are no pre-edit attributes without a pre-edit.
+            (_, None) if style == Style::None => {
+                ImeContext::create_ic(xconn, im, style, window, None)
+            },
+            (ic_spot, client_data) => {
+                let ic_spot = ic_spot.unwrap_or_else(|| ffi::XPoint { x: 0, y: 0 });
+                let pre_edit_attr = if let Some(client_data) = client_data {
+                    create_pre_edit_attr_with_callbacks(xconn, &ic_spot, &client_data.callbacks)
+                } else {
+                    create_pre_edit_attr(xconn, &ic_spot)
+                };
+                ImeContext::create_ic(xconn, im, style, window, Some(&pre_edit_attr))
+            },
         };
 
         let ic = ic.ok_or(ImeContextCreationError::Null)?;
@@ -54,46 +364,41 @@ impl ImeContext {
         Ok(ImeContext {
             ic,
             ic_spot: ic_spot.unwrap_or_else(|| ffi::XPoint { x: 0, y: 0 }),
+            ic_area: None,
+            style,
+            is_focused: false,
+            _client_data: client_data,
         })
     }
 
     unsafe fn create_ic(
         xconn: &Arc<XConnection>,
         im: ffi::XIM,
+        style: Style,
         window: ffi::Window,
+        pre_edit_attr: Option<&util::XSmartPointer<c_void>>,
     ) -> Option<ffi::XIC> {
-        let ic = (xconn.xlib.XCreateIC)(
-            im,
-            ffi::XNInputStyle_0.as_ptr() as *const _,
-            ffi::XIMPreeditNothing | ffi::XIMStatusNothing,
-            ffi::XNClientWindow_0.as_ptr() as *const _,
-            window,
-            ptr::null_mut::<()>(),
-        );
-        if ic.is_null() {
-            None
+        let ic = if let Some(pre_edit_attr) = pre_edit_attr {
+            (xconn.xlib.XCreateIC)(
+                im,
+                ffi::XNInputStyle_0.as_ptr() as *const _,
+                style.input_style(),
+                ffi::XNClientWindow_0.as_ptr() as *const _,
+                window,
+                ffi::XNPreeditAttributes_0.as_ptr() as *const _,
+                pre_edit_attr.ptr,
+                ptr::null_mut::<()>(),
+            )
         } else {
-            Some(ic)
-        }
-    }
-
-    unsafe fn create_ic_with_spot(
-        xconn: &Arc<XConnection>,
-        im: ffi::XIM,
-        window: ffi::Window,
-        ic_spot: ffi::XPoint,
-    ) -> Option<ffi::XIC> {
-        let pre_edit_attr = create_pre_edit_attr(xconn, &ic_spot);
-        let ic = (xconn.xlib.XCreateIC)(
-            im,
-            ffi::XNInputStyle_0.as_ptr() as *const _,
-            ffi::XIMPreeditNothing | ffi::XIMStatusNothing,
-            ffi::XNClientWindow_0.as_ptr() as *const _,
-            window,
-            ffi::XNPreeditAttributes_0.as_ptr() as *const _,
-            pre_edit_attr.ptr,
-            ptr::null_mut::<()>(),
-        );
+            (xconn.xlib.XCreateIC)(
+                im,
+                ffi::XNInputStyle_0.as_ptr() as *const _,
+                style.input_style(),
+                ffi::XNClientWindow_0.as_ptr() as *const _,
+                window,
+                ptr::null_mut::<()>(),
+            )
+        };
         if ic.is_null() {
             None
         } else {
@@ -101,25 +406,32 @@ impl ImeContext {
         }
     }
 
-    pub fn focus(&self, xconn: &Arc<XConnection>) -> Result<(), XError> {
+    pub fn focus(&mut self, xconn: &Arc<XConnection>) -> Result<(), XError> {
         unsafe {
             (xconn.xlib.XSetICFocus)(self.ic);
         }
+        self.is_focused = true;
         xconn.check_errors()
     }
 
-    pub fn unfocus(&self, xconn: &Arc<XConnection>) -> Result<(), XError> {
+    pub fn unfocus(&mut self, xconn: &Arc<XConnection>) -> Result<(), XError> {
         unsafe {
             (xconn.xlib.XUnsetICFocus)(self.ic);
         }
+        self.is_focused = false;
         xconn.check_errors()
     }
 
     pub fn set_spot(&mut self, xconn: &Arc<XConnection>, x: c_short, y: c_short) {
-        if self.ic_spot.x == x && self.ic_spot.y == y {
+        if self.ic_spot.x == x && self.ic_spot.y == y && self.ic_area.is_none() {
             return;
         }
         self.ic_spot = ffi::XPoint { x, y };
+        self.ic_area = None;
+        // The spot is kept so that it can be applied once the IME is allowed again.
+        if self.style == Style::None {
+            return;
+        }
 
         unsafe {
             let pre_edit_attr = create_pre_edit_attr(xconn, &self.ic_spot);
@@ -131,4 +443,35 @@ impl ImeContext {
             );
         }
     }
+
+    // The spot is placed on the bottom edge of the area, which is where the input method expects
+    // the baseline of the text to be.
+    pub fn set_area(
+        &mut self,
+        xconn: &Arc<XConnection>,
+        x: c_short,
+        y: c_short,
+        width: c_ushort,
+        height: c_ushort,
+    ) {
+        let ic_area = ffi::XRectangle { x, y, width, height };
+        if self.ic_area == Some(ic_area) {
+            return;
+        }
+        self.ic_spot = ffi::XPoint { x, y: y.saturating_add(height as c_short) };
+        self.ic_area = Some(ic_area);
+        if self.style == Style::None {
+            return;
+        }
+
+        unsafe {
+            let pre_edit_attr = create_pre_edit_attr_with_area(xconn, &self.ic_spot, &ic_area);
+            (xconn.xlib.XSetICValues)(
+                self.ic,
+                ffi::XNPreeditAttributes_0.as_ptr() as *const _,
+                pre_edit_attr.ptr,
+                ptr::null_mut::<()>(),
+            );
+        }
+    }
 }
@@ -1,12 +1,12 @@
 use std::mem;
 use std::ptr;
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use super::{ffi, XConnection, XError};
+use super::{ffi, ImeEventSender, XConnection, XError};
 
 use super::input_method::PotentialInputMethods;
-use super::context::ImeContext;
+use super::context::{ImeContext, Style};
 
 pub unsafe fn close_im(xconn: &Arc<XConnection>, im: ffi::XIM) -> Result<(), XError> {
     (xconn.xlib.XCloseIM)(im);
@@ -24,6 +24,15 @@ pub struct ImeInner {
     pub im: ffi::XIM,
     pub potential_input_methods: PotentialInputMethods,
     pub contexts: HashMap<ffi::Window, Option<ImeContext>>,
+    // The pre-edit style new input contexts are created with, as supported by `im`.
+    pub style: Style,
+    // Whether `im` supports `Style::None`, which windows that disallowed it use.
+    pub none_supported: bool,
+    // The windows that disallowed the IME, which keep doing so when it's replaced.
+    pub disallowed: HashSet<ffi::Window>,
+    // The window with the keyboard focus, if it has a context.
+    pub focused_window: Option<ffi::Window>,
+    pub event_sender: ImeEventSender,
     // WARNING: this is initially zeroed!
     pub destroy_callback: ffi::XIMCallback,
     // Indicates whether or not the the input method was destroyed on the server end
@@ -36,18 +45,34 @@ impl ImeInner {
     pub fn new(
         xconn: Arc<XConnection>,
         potential_input_methods: PotentialInputMethods,
+        event_sender: ImeEventSender,
     ) -> Self {
         ImeInner {
             xconn,
             im: ptr::null_mut(),
             potential_input_methods,
             contexts: HashMap::new(),
+            style: Style::Nothing,
+            none_supported: false,
+            disallowed: HashSet::new(),
+            focused_window: None,
+            event_sender,
             destroy_callback: unsafe { mem::zeroed() },
             is_destroyed: false,
             is_fallback: false,
         }
     }
 
+    // Windows that disallowed the IME bypass it when it supports that, and otherwise keep a context
+    // of the usual style that's never focused.
+    pub fn style_for(&self, window: ffi::Window) -> Style {
+        if self.disallowed.contains(&window) && self.none_supported {
+            Style::None
+        } else {
+            self.style
+        }
+    }
+
     pub unsafe fn close_im_if_necessary(&self) -> Result<bool, XError> {
         if !self.is_destroyed {
             close_im(&self.xconn, self.im).map(|_| true)
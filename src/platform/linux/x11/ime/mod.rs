@@ -12,11 +12,33 @@ use super::{ffi, util, XConnection, XError};
 
 use self::inner::{close_im, ImeInner};
 use self::input_method::PotentialInputMethods;
-use self::context::{ImeContextCreationError, ImeContext};
+use self::context::{ImeContextCreationError, ImeContext, Style};
 use self::callbacks::*;
 
-pub type ImeReceiver = Receiver<(ffi::Window, i16, i16)>;
-pub type ImeSender = Sender<(ffi::Window, i16, i16)>;
+pub type ImeReceiver = Receiver<ImeRequest>;
+pub type ImeSender = Sender<ImeRequest>;
+pub type ImeEventReceiver = Receiver<(ffi::Window, ImeEvent)>;
+pub type ImeEventSender = Sender<(ffi::Window, ImeEvent)>;
+
+/// Requests sent from windows to the event loop, which owns the input contexts.
+#[derive(Debug)]
+pub enum ImeRequest {
+    /// Moves the pre-edit spot of a window.
+    Position(ffi::Window, i16, i16),
+    /// Sets the area of a window that the pre-edit text is being entered in.
+    Area(ffi::Window, i16, i16, u16, u16),
+    /// Allows or disallows the input method for a window.
+    Allowed(ffi::Window, bool),
+}
+
+/// Pre-edit updates reported by input contexts using `XIMPreeditCallbacks`.
+#[derive(Debug)]
+pub enum ImeEvent {
+    Start,
+    /// The pre-edit text and the cursor position in it, as a byte offset.
+    Update(String, usize),
+    End,
+}
 
 #[derive(Debug)]
 pub enum ImeCreationError {
@@ -32,13 +54,17 @@ pub struct Ime {
 }
 
 impl Ime {
-    pub fn new(xconn: Arc<XConnection>) -> Result<Self, ImeCreationError> {
+    pub fn new(
+        xconn: Arc<XConnection>,
+        event_sender: ImeEventSender,
+    ) -> Result<Self, ImeCreationError> {
         let potential_input_methods = PotentialInputMethods::new(&xconn);
 
         let (mut inner, client_data) = {
             let mut inner = Box::new(ImeInner::new(
                 xconn,
                 potential_input_methods,
+                event_sender,
             ));
             let inner_ptr = Box::into_raw(inner);
             let client_data = inner_ptr as _;
@@ -60,6 +86,8 @@ impl Ime {
         let is_fallback = input_method.is_fallback();
         if let Some(input_method) = input_method.ok() {
             inner.im = input_method.im;
+            inner.style = unsafe { Style::preferred(&xconn, input_method.im) };
+            inner.none_supported = unsafe { Style::None.is_supported(&xconn, input_method.im) };
             inner.is_fallback = is_fallback;
             unsafe {
                 let result = set_destroy_callback(&xconn, input_method.im, &*inner)
@@ -83,18 +111,24 @@ impl Ime {
     // Ok(_) indicates that nothing went wrong internally
     // Ok(true) indicates that the action was actually performed
     // Ok(false) indicates that the action is not presently applicable
-    pub fn create_context(&mut self, window: ffi::Window)
+    pub fn create_context(&mut self, window: ffi::Window, allowed: bool)
         -> Result<bool, ImeContextCreationError>
     {
+        if !allowed {
+            self.inner.disallowed.insert(window);
+        }
         let context = if self.is_destroyed() {
             // Create empty entry in map, so that when IME is rebuilt, this window has a context.
             None
         } else {
+            let style = self.inner.style_for(window);
             Some(unsafe { ImeContext::new(
                 &self.inner.xconn,
                 self.inner.im,
+                style,
                 window,
                 None,
+                self.inner.event_sender.clone(),
             ) }?)
         };
         self.inner.contexts.insert(window, context);
@@ -113,6 +147,10 @@ impl Ime {
     }
 
     pub fn remove_context(&mut self, window: ffi::Window) -> Result<bool, XError> {
+        self.inner.disallowed.remove(&window);
+        if self.inner.focused_window == Some(window) {
+            self.inner.focused_window = None;
+        }
         if let Some(Some(context)) = self.inner.contexts.remove(&window) {
             unsafe {
                 self.inner.destroy_ic_if_necessary(context.ic)?;
@@ -124,7 +162,8 @@ impl Ime {
     }
 
     pub fn focus(&mut self, window: ffi::Window) -> Result<bool, XError> {
-        if self.is_destroyed() {
+        self.inner.focused_window = Some(window);
+        if self.is_destroyed() || self.inner.disallowed.contains(&window) {
             return Ok(false);
         }
         if let Some(&mut Some(ref mut context)) = self.inner.contexts.get_mut(&window) {
@@ -135,10 +174,16 @@ impl Ime {
     }
 
     pub fn unfocus(&mut self, window: ffi::Window) -> Result<bool, XError> {
+        if self.inner.focused_window == Some(window) {
+            self.inner.focused_window = None;
+        }
         if self.is_destroyed() {
             return Ok(false);
         }
         if let Some(&mut Some(ref mut context)) = self.inner.contexts.get_mut(&window) {
+            if !context.is_focused {
+                return Ok(false);
+            }
             context.unfocus(&self.xconn).map(|_| true)
         } else {
             Ok(false)
@@ -153,6 +198,73 @@ impl Ime {
             context.set_spot(&self.xconn, x as _, y as _);
         }
     }
+
+    pub fn send_xim_area(&mut self, window: ffi::Window, x: i16, y: i16, width: u16, height: u16) {
+        if self.is_destroyed() {
+            return;
+        }
+        if let Some(&mut Some(ref mut context)) = self.inner.contexts.get_mut(&window) {
+            context.set_area(&self.xconn, x as _, y as _, width as _, height as _);
+        }
+    }
+
+    // Gives the window a context of the appropriate style. A disallowed context still exists, since
+    // we need it for `Xutf8LookupString`, but it's never focused, so the IME doesn't filter any
+    // events. The choice is remembered if the IME is gone, and applied once it comes back.
+    pub fn set_ime_allowed(&mut self, window: ffi::Window, allowed: bool)
+        -> Result<bool, ImeContextCreationError>
+    {
+        let changed = if allowed {
+            self.inner.disallowed.remove(&window)
+        } else {
+            self.inner.disallowed.insert(window)
+        };
+        if !changed || self.is_destroyed() {
+            return Ok(false);
+        }
+        let style = self.inner.style_for(window);
+        let is_focused = self.inner.focused_window == Some(window);
+        let (ic_spot, ic_area) = match self.inner.contexts.get_mut(&window) {
+            Some(&mut Some(ref mut context)) => {
+                // This is all that changes without `Style::None`, and it keeps applying if the
+                // context can't be replaced.
+                if is_focused && allowed {
+                    context.focus(&self.xconn).map_err(ImeContextCreationError::XError)?;
+                } else if context.is_focused {
+                    context.unfocus(&self.xconn).map_err(ImeContextCreationError::XError)?;
+                }
+                if context.style == style {
+                    return Ok(true);
+                }
+                (context.ic_spot, context.ic_area)
+            },
+            _ => return Ok(false),
+        };
+
+        // The old context is only destroyed once the new one exists, so that the window always has
+        // one.
+        let mut context = unsafe { ImeContext::new(
+            &self.inner.xconn,
+            self.inner.im,
+            style,
+            window,
+            Some(ic_spot),
+            self.inner.event_sender.clone(),
+        ) }?;
+        if let Some(area) = ic_area {
+            context.set_area(&self.xconn, area.x, area.y, area.width, area.height);
+        }
+        if is_focused && allowed {
+            context.focus(&self.xconn).map_err(ImeContextCreationError::XError)?;
+        }
+        if let Some(Some(old_context)) = self.inner.contexts.insert(window, Some(context)) {
+            unsafe {
+                self.inner.destroy_ic_if_necessary(old_context.ic)
+                    .map_err(ImeContextCreationError::XError)?;
+            }
+        }
+        Ok(true)
+    }
 }
 
 impl Drop for Ime {
@@ -1,11 +1,12 @@
 #![cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd", target_os = "openbsd"))]
 
 pub mod ffi;
-mod events;
 mod monitor;
 mod window;
 mod xdisplay;
 mod dnd;
+mod clipboard;
+mod drag;
 mod ime;
 pub mod util;
 
@@ -14,7 +15,7 @@ pub use self::window::UnownedWindow;
 pub use self::xdisplay::{XConnection, XNotSupported, XError};
 
 use std::{mem, ptr, slice};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::ffi::CStr;
 use std::ops::Deref;
@@ -23,6 +24,7 @@ use std::sync::{Arc, mpsc, Weak};
 use std::sync::atomic::{self, AtomicBool};
 
 use libc::{self, setlocale, LC_CTYPE};
+use parking_lot::Mutex;
 
 use {
     ControlFlow,
@@ -33,25 +35,48 @@ use {
     KeyboardInput,
     LogicalPosition,
     LogicalSize,
+    PointerState,
     WindowAttributes,
     WindowEvent,
 };
-use events::ModifiersState;
+use events::{ModifiersState, MouseScrollDelta};
 use platform::PlatformSpecificWindowBuilderAttributes;
+use super::cursor::ThemeOverride;
+use super::keysym::keysym_to_vkey;
 use self::dnd::{Dnd, DndState};
-use self::ime::{ImeReceiver, ImeSender, ImeCreationError, Ime};
+use self::clipboard::Clipboard;
+use self::drag::DragSource;
+use self::ime::{
+    ImeReceiver,
+    ImeSender,
+    ImeRequest,
+    ImeEventReceiver,
+    ImeEvent,
+    ImeCreationError,
+    Ime,
+};
 
 pub struct EventsLoop {
     xconn: Arc<XConnection>,
     wm_delete_window: ffi::Atom,
     dnd: Dnd,
+    clipboard: Arc<Clipboard>,
+    drag_source: Arc<DragSource>,
+    cursor_theme: Arc<Mutex<ThemeOverride>>,
     ime_receiver: ImeReceiver,
     ime_sender: ImeSender,
+    ime_event_receiver: ImeEventReceiver,
     ime: RefCell<Ime>,
     randr_event_offset: c_int,
     windows: RefCell<HashMap<WindowId, Weak<UnownedWindow>>>,
     devices: RefCell<HashMap<DeviceId, Device>>,
     xi2ext: XExtension,
+    // Whether the server sends the gesture events of XInput 2.4
+    xi2_gestures: bool,
+    // The scale of the pinch gesture in progress, relative to when it started
+    pinch_scale: Cell<f64>,
+    // The window the pointer last entered, until it leaves it
+    pointer_window: Cell<Option<ffi::Window>>,
     pending_wakeup: Arc<AtomicBool>,
     root: ffi::Window,
     // A dummy, `InputOnly` window that we can use to receive wakeup events and interrupt blocking
@@ -74,13 +99,16 @@ impl EventsLoop {
 
         let dnd = Dnd::new(Arc::clone(&xconn))
             .expect("Failed to call XInternAtoms when initializing drag and drop");
+        let drag_source = DragSource::new(Arc::clone(&xconn))
+            .expect("Failed to call XInternAtoms when initializing drag and drop");
 
         let (ime_sender, ime_receiver) = mpsc::channel();
+        let (ime_event_sender, ime_event_receiver) = mpsc::channel();
         // Input methods will open successfully without setting the locale, but it won't be
         // possible to actually commit pre-edit sequences.
         unsafe { setlocale(LC_CTYPE, b"\0".as_ptr() as *const _); }
         let ime = RefCell::new({
-            let result = Ime::new(Arc::clone(&xconn));
+            let result = Ime::new(Arc::clone(&xconn), ime_event_sender);
             if let Err(ImeCreationError::OpenFailure(ref state)) = result {
                 panic!(format!("Failed to open input method: {:#?}", state));
             }
@@ -108,9 +136,11 @@ impl EventsLoop {
             result
         };
 
-        unsafe {
+        let xi2_gestures = unsafe {
+            // Gesture events are only sent to clients asking for XInput 2.4, which is otherwise the
+            // same as the version we need.
             let mut xinput_major_ver = ffi::XI_2_Major;
-            let mut xinput_minor_ver = ffi::XI_2_Minor;
+            let mut xinput_minor_ver = 4;
             if (xconn.xinput2.XIQueryVersion)(
                 xconn.display,
                 &mut xinput_major_ver,
@@ -122,7 +152,8 @@ impl EventsLoop {
                     xinput_minor_ver,
                 );
             }
-        }
+            (xinput_major_ver, xinput_minor_ver) >= (2, 4)
+        };
 
         xconn.update_cached_wm_info(root);
 
@@ -142,17 +173,27 @@ impl EventsLoop {
             )
         };
 
+        let clipboard = Clipboard::new(Arc::clone(&xconn), wakeup_dummy_window)
+            .expect("Failed to call XInternAtoms when initializing the clipboard");
+
         let result = EventsLoop {
             xconn,
             wm_delete_window,
             dnd,
+            clipboard: Arc::new(clipboard),
+            drag_source: Arc::new(drag_source),
+            cursor_theme: Default::default(),
             ime_receiver,
             ime_sender,
+            ime_event_receiver,
             ime,
             randr_event_offset,
             windows: Default::default(),
             devices: Default::default(),
             xi2ext,
+            xi2_gestures,
+            pinch_scale: Cell::new(1.0),
+            pointer_window: Cell::new(None),
             pending_wakeup: Default::default(),
             root,
             wakeup_dummy_window,
@@ -177,6 +218,37 @@ impl EventsLoop {
         &self.xconn
     }
 
+    pub fn set_cursor_theme(&self, theme: Option<&str>, size: Option<u32>) {
+        *self.cursor_theme.lock() = ThemeOverride { name: theme.map(str::to_owned), size };
+        for window in self.windows.borrow().values().filter_map(Weak::upgrade) {
+            window.reload_cursor();
+        }
+    }
+
+    pub fn get_pointer_state(&self) -> Option<PointerState> {
+        // Querying the pointer relative to the window it's over gives the screen position as well.
+        let xwindow = self.pointer_window.get()
+            .and_then(|xwindow| self.with_window(xwindow, |window| window.get_hidpi_factor())
+                .map(|dpi_factor| (xwindow, dpi_factor)));
+        let pointer = self.xconn.query_pointer(
+            xwindow.map(|(xwindow, _)| xwindow).unwrap_or(self.root),
+            util::VIRTUAL_CORE_POINTER,
+        ).ok()?;
+        let window = match xwindow {
+            Some((xwindow, dpi_factor)) if pointer.relative_to_window => {
+                let position = LogicalPosition::from_physical((pointer.win_x, pointer.win_y), dpi_factor);
+                Some((mkwid(xwindow), position))
+            }
+            _ => None,
+        };
+        Some(PointerState {
+            root_position: Some((pointer.root_x, pointer.root_y).into()),
+            window,
+            buttons: pointer.get_pressed_buttons(),
+            modifiers: pointer.get_modifier_state(),
+        })
+    }
+
     pub fn create_proxy(&self) -> EventsLoopProxy {
         EventsLoopProxy {
             pending_wakeup: Arc::downgrade(&self.pending_wakeup),
@@ -202,6 +274,7 @@ impl EventsLoop {
             }
             self.process_event(&mut xev, &mut callback);
         }
+        self.check_drag_timeout(&mut callback);
     }
 
     pub fn run_forever<F>(&mut self, mut callback: F)
@@ -210,8 +283,6 @@ impl EventsLoop {
         let mut xev = unsafe { mem::uninitialized() };
 
         loop {
-            unsafe { (self.xconn.xlib.XNextEvent)(self.xconn.display, &mut xev) }; // Blocks as necessary
-
             let mut control_flow = ControlFlow::Continue;
 
             // Track whether or not `Break` was returned when processing the event.
@@ -222,7 +293,14 @@ impl EventsLoop {
                     }
                 };
 
-                self.process_event(&mut xev, &mut cb);
+                // Blocks as necessary, but only until a drop we made has to be given up on.
+                let count = unsafe { (self.xconn.xlib.XPending)(self.xconn.display) };
+                if count == 0 && !self.xconn.wait_for_events(self.drag_source.timeout()) {
+                    self.check_drag_timeout(&mut cb);
+                } else {
+                    unsafe { (self.xconn.xlib.XNextEvent)(self.xconn.display, &mut xev) };
+                    self.process_event(&mut xev, &mut cb);
+                }
             }
 
             if let ControlFlow::Break = control_flow {
@@ -231,6 +309,17 @@ impl EventsLoop {
         }
     }
 
+    fn check_drag_timeout<F>(&self, mut callback: F)
+        where F: FnMut(Event)
+    {
+        if let Some(source) = self.drag_source.check_timeout() {
+            callback(Event::WindowEvent {
+                window_id: mkwid(source),
+                event: WindowEvent::DragEnded { action: None },
+            });
+        }
+    }
+
     fn process_event<F>(&mut self, xev: &mut ffi::XEvent, mut callback: F)
         where F: FnMut(Event)
     {
@@ -238,10 +327,38 @@ impl EventsLoop {
         // Specifically, this involves all of the KeyPress events in compose/pre-edit sequences,
         // along with an extra copy of the KeyRelease events. This also prevents backspace and
         // arrow keys from being detected twice.
-        if ffi::True == unsafe { (self.xconn.xlib.XFilterEvent)(
+        let filtered = ffi::True == unsafe { (self.xconn.xlib.XFilterEvent)(
             xev,
             { let xev: &ffi::XAnyEvent = xev.as_ref(); xev.window }
-        ) } {
+        ) };
+
+        // Selections are taken and requested at the time of the last input event.
+        match xev.get_type() {
+            ffi::KeyPress | ffi::KeyRelease => {
+                let xkey: &ffi::XKeyEvent = xev.as_ref();
+                self.clipboard.set_time(xkey.time);
+            }
+            ffi::ButtonPress | ffi::ButtonRelease => {
+                let xbutton: &ffi::XButtonEvent = xev.as_ref();
+                self.clipboard.set_time(xbutton.time);
+            }
+            _ => (),
+        }
+
+        // The pre-edit callbacks are run from within `XFilterEvent`.
+        while let Ok((window, event)) = self.ime_event_receiver.try_recv() {
+            let (text, cursor_range) = match event {
+                ImeEvent::Start => continue,
+                ImeEvent::Update(text, cursor) => (text, Some((cursor, cursor))),
+                ImeEvent::End => (String::new(), None),
+            };
+            callback(Event::WindowEvent {
+                window_id: mkwid(window),
+                event: WindowEvent::ImePreedit { text, cursor_range },
+            });
+        }
+
+        if filtered {
             return;
         }
 
@@ -264,6 +381,10 @@ impl EventsLoop {
                     let source_window = client_msg.data.get_long(0) as c_ulong;
                     let flags = client_msg.data.get_long(1);
                     let version = flags >> 24;
+                    // Each drag starts out accepted, until the application answers otherwise.
+                    self.with_window(window, |window| {
+                        *window.drop_response.lock() = Default::default();
+                    });
                     self.dnd.version = Some(version);
                     let has_more_types = flags - (flags & (c_long::max_value() - 1)) == 1;
                     if !has_more_types {
@@ -281,9 +402,7 @@ impl EventsLoop {
                     // over our window. We emit HoveredFile in response; while the Mac OS X backend
                     // does that upon a drag entering, XDnD doesn't have access to the actual drop
                     // data until this event. For parity with other platforms, we only emit
-                    // HoveredFile the first time, though if winit's API is later extended to
-                    // supply position updates with HoveredFile or another event, implementing
-                    // that here would be trivial.
+                    // HoveredFile the first time, and DragMoved every time.
 
                     let source_window = client_msg.data.get_long(0) as c_ulong;
 
@@ -291,10 +410,10 @@ impl EventsLoop {
                     // where shift = mem::size_of::<c_short>() * 8
                     // Note that coordinates are in "desktop space", not "window space"
                     // (in x11 parlance, they're root window coordinates)
-                    //let packed_coordinates = client_msg.data.get_long(2);
-                    //let shift = mem::size_of::<libc::c_short>() * 8;
-                    //let x = packed_coordinates >> shift;
-                    //let y = packed_coordinates & !(x << shift);
+                    let packed_coordinates = client_msg.data.get_long(2);
+                    let shift = mem::size_of::<c_short>() * 8;
+                    let x_rel_root = ((packed_coordinates >> shift) & 0xffff) as c_short as i32;
+                    let y_rel_root = (packed_coordinates & 0xffff) as c_short as i32;
 
                     // By our own state flow, version should never be None at this point.
                     let version = self.dnd.version.unwrap_or(5);
@@ -302,31 +421,79 @@ impl EventsLoop {
                     // Action is specified in versions 2 and up, though we don't need it anyway.
                     //let action = client_msg.data.get_long(4);
 
-                    let accepted = if let Some(ref type_list) = self.dnd.type_list {
-                        type_list.contains(&self.dnd.atoms.uri_list)
+                    // This is sent before we decide whether to accept the drag, so the application
+                    // can answer it for the position it's at.
+                    let inner_position = self.xconn.translate_coords(window, self.root).ok();
+                    let dpi_factor = self.with_window(window, |window| window.get_hidpi_factor());
+                    if let (Some(inner_position), Some(dpi_factor)) = (inner_position.as_ref(), dpi_factor) {
+                        let position = LogicalPosition::from_physical(
+                            (
+                                (x_rel_root - inner_position.x_rel_root) as f64,
+                                (y_rel_root - inner_position.y_rel_root) as f64,
+                            ),
+                            dpi_factor,
+                        );
+                        callback(Event::WindowEvent {
+                            window_id,
+                            event: WindowEvent::DragMoved { position },
+                        });
+                    }
+
+                    let accepted_types = self.with_window(window, |window| {
+                        window.accepted_drop_types.lock().clone()
+                    }).unwrap_or_default();
+                    let (has_files, data_type) = if let Some(ref type_list) = self.dnd.type_list {
+                        (
+                            type_list.contains(&self.dnd.atoms.uri_list),
+                            accepted_types.into_iter().find(|t| type_list.contains(t)),
+                        )
                     } else {
-                        false
+                        (false, None)
                     };
 
-                    if accepted {
+                    if has_files || data_type.is_some() {
                         self.dnd.source_window = Some(source_window);
+                        self.dnd.data_type = data_type;
+                        if has_files && self.dnd.result.is_none() {
+                            let time = if version >= 1 {
+                                client_msg.data.get_long(3) as c_ulong
+                            } else {
+                                // In version 0, time isn't specified
+                                ffi::CurrentTime
+                            };
+                            // This results in the SelectionNotify event below
+                            unsafe { self.dnd.convert_selection(window, self.dnd.atoms.uri_list, time) };
+                        }
+
+                        // The application has had the chance to answer DragMoved by now.
+                        let response = self.with_window(window, |window| *window.drop_response.lock())
+                            .unwrap_or_default();
+                        self.dnd.accepted_action = response.action
+                            .map(|action| self.dnd.atoms.action_to_atom(action));
+                        let state = match self.dnd.accepted_action {
+                            Some(action) => DndState::Accepted(action),
+                            None => DndState::Rejected,
+                        };
+                        let area = match (response.area, inner_position, dpi_factor) {
+                            (Some((position, size)), Some(inner_position), Some(dpi_factor)) => {
+                                let (x, y): (f64, f64) = position.to_physical(dpi_factor).into();
+                                let (width, height): (f64, f64) = size.to_physical(dpi_factor).into();
+                                Some((
+                                    (x as i32 + inner_position.x_rel_root) as c_short,
+                                    (y as i32 + inner_position.y_rel_root) as c_short,
+                                    width as c_ushort,
+                                    height as c_ushort,
+                                ))
+                            },
+                            _ => None,
+                        };
                         unsafe {
-                            if self.dnd.result.is_none() {
-                                let time = if version >= 1 {
-                                    client_msg.data.get_long(3) as c_ulong
-                                } else {
-                                    // In version 0, time isn't specified
-                                    ffi::CurrentTime
-                                };
-                                // This results in the SelectionNotify event below
-                                self.dnd.convert_selection(window, time);
-                            }
-                            self.dnd.send_status(window, source_window, DndState::Accepted)
+                            self.dnd.send_status(window, source_window, state, area)
                                 .expect("Failed to send XDnD status message.");
                         }
                     } else {
                         unsafe {
-                            self.dnd.send_status(window, source_window, DndState::Rejected)
+                            self.dnd.send_status(window, source_window, DndState::Rejected, None)
                                 .expect("Failed to send XDnD status message.");
                             self.dnd.send_finished(window, source_window, DndState::Rejected)
                                 .expect("Failed to send XDnD finished message.");
@@ -334,7 +501,17 @@ impl EventsLoop {
                         self.dnd.reset();
                     }
                 } else if client_msg.message_type == self.dnd.atoms.drop {
-                    if let Some(source_window) = self.dnd.source_window {
+                    if let (Some(source_window), None) = (self.dnd.source_window, self.dnd.accepted_action) {
+                        // The application rejected the drop where it happened.
+                        unsafe {
+                            self.dnd.send_finished(window, source_window, DndState::Rejected)
+                                .expect("Failed to send XDnD finished message.");
+                        }
+                        callback(Event::WindowEvent {
+                            window_id,
+                            event: WindowEvent::HoveredFileCancelled,
+                        });
+                    } else if let Some(source_window) = self.dnd.source_window {
                         if let Some(Ok(ref path_list)) = self.dnd.result {
                             for path in path_list {
                                 callback(Event::WindowEvent {
@@ -343,12 +520,33 @@ impl EventsLoop {
                                 });
                             }
                         }
-                        unsafe {
-                            self.dnd.send_finished(window, source_window, DndState::Accepted)
-                                .expect("Failed to send XDnD finished message.");
+                        if let Some(data_type) = self.dnd.data_type {
+                            // We finish once the data has arrived, in the SelectionNotify event
+                            // below.
+                            let time = client_msg.data.get_long(2) as c_ulong;
+                            unsafe { self.dnd.convert_selection(window, data_type, time) };
+                            self.dnd.awaiting_data = true;
+                        } else {
+                            let action = self.dnd.accepted_action.unwrap_or(self.dnd.atoms.action_copy);
+                            unsafe {
+                                self.dnd.send_finished(window, source_window, DndState::Accepted(action))
+                                    .expect("Failed to send XDnD finished message.");
+                            }
                         }
                     }
-                    self.dnd.reset();
+                    if !self.dnd.awaiting_data {
+                        self.dnd.reset();
+                    }
+                } else if client_msg.message_type == self.dnd.atoms.status {
+                    // This is the target of a drag we started answering our XdndPosition
+                    self.drag_source.handle_status(client_msg);
+                } else if client_msg.message_type == self.dnd.atoms.finished {
+                    if let Some((source, action)) = self.drag_source.handle_finished(client_msg) {
+                        callback(Event::WindowEvent {
+                            window_id: mkwid(source),
+                            event: WindowEvent::DragEnded { action },
+                        });
+                    }
                 } else if client_msg.message_type == self.dnd.atoms.leave {
                     self.dnd.reset();
                     callback(Event::WindowEvent {
@@ -367,11 +565,35 @@ impl EventsLoop {
                 let window = xsel.requestor;
                 let window_id = mkwid(window);
 
-                if xsel.property == self.dnd.atoms.selection {
+                if xsel.selection == self.dnd.atoms.selection && self.dnd.awaiting_data {
+                    // This is where we receive dropped data other than files
+                    let data = if xsel.property == self.dnd.atoms.selection {
+                        unsafe { self.dnd.read_data(window, xsel.target) }.ok()
+                    } else {
+                        None
+                    };
+                    let mime_type = self.xconn.get_atom_name(xsel.target);
+                    let state = if let (Some(data), Some(mime_type)) = (data, mime_type) {
+                        callback(Event::WindowEvent {
+                            window_id,
+                            event: WindowEvent::DroppedData { mime_type, data },
+                        });
+                        DndState::Accepted(self.dnd.accepted_action.unwrap_or(self.dnd.atoms.action_copy))
+                    } else {
+                        DndState::Rejected
+                    };
+                    if let Some(source_window) = self.dnd.source_window {
+                        unsafe {
+                            self.dnd.send_finished(window, source_window, state)
+                                .expect("Failed to send XDnD finished message.");
+                        }
+                    }
+                    self.dnd.reset();
+                } else if xsel.property == self.dnd.atoms.selection {
                     let mut result = None;
 
                     // This is where we receive data from drag and drop
-                    if let Ok(mut data) = unsafe { self.dnd.read_data(window) } {
+                    if let Ok(mut data) = unsafe { self.dnd.read_data(window, self.dnd.atoms.uri_list) } {
                         let parse_result = self.dnd.parse_data(&mut data);
                         if let Ok(ref path_list) = parse_result {
                             for path in path_list {
@@ -385,9 +607,47 @@ impl EventsLoop {
                     }
 
                     self.dnd.result = result;
+                } else if xsel.requestor == self.clipboard.window {
+                    self.clipboard.handle_notify(xsel);
+                }
+            }
+
+            ffi::SelectionRequest => {
+                let xsel: &ffi::XSelectionRequestEvent = xev.as_ref();
+                if xsel.owner == self.clipboard.window {
+                    self.clipboard.handle_request(xsel);
+                }
+            }
+
+            ffi::SelectionClear => {
+                let xsel: &ffi::XSelectionClearEvent = xev.as_ref();
+                if xsel.window == self.clipboard.window {
+                    self.clipboard.handle_clear(xsel);
                 }
             }
 
+            // We only receive core pointer events while the pointer is grabbed for a drag we
+            // started; everything else comes through XInput2.
+            ffi::MotionNotify => {
+                let xev: &ffi::XMotionEvent = xev.as_ref();
+                self.drag_source.handle_motion(xev.x_root, xev.y_root, xev.time);
+            }
+
+            ffi::ButtonRelease => {
+                let xev: &ffi::XButtonEvent = xev.as_ref();
+                if let Some((source, action)) = self.drag_source.handle_release(xev.time) {
+                    callback(Event::WindowEvent {
+                        window_id: mkwid(source),
+                        event: WindowEvent::DragEnded { action },
+                    });
+                }
+            }
+
+            ffi::PropertyNotify => {
+                let xprop: &ffi::XPropertyEvent = xev.as_ref();
+                self.clipboard.handle_property(xprop);
+            }
+
             ffi::ConfigureNotify => {
                 #[derive(Debug, Default)]
                 struct Events {
@@ -618,7 +878,7 @@ impl EventsLoop {
                         self.xconn.check_errors().expect("Failed to lookup keysym");
                         keysym
                     };
-                    let virtual_keycode = events::keysym_to_element(keysym as c_uint);
+                    let virtual_keycode = keysym_to_vkey(keysym as u32);
 
                     callback(Event::WindowEvent {
                         window_id,
@@ -641,6 +901,14 @@ impl EventsLoop {
                         return;
                     };
 
+                    // A keycode of 0 means the text came from the input method.
+                    if xkev.keycode == 0 && !written.is_empty() {
+                        callback(Event::WindowEvent {
+                            window_id,
+                            event: WindowEvent::ImeCommit(written.clone()),
+                        });
+                    }
+
                     for chr in written.chars() {
                         let event = Event::WindowEvent {
                             window_id,
@@ -660,10 +928,19 @@ impl EventsLoop {
 
                 use events::WindowEvent::{Focused, CursorEntered, MouseInput, CursorLeft, CursorMoved, MouseWheel, AxisMotion};
                 use events::ElementState::{Pressed, Released};
-                use events::MouseButton::{Left, Right, Middle, Other};
+                use events::MouseButton::{Left, Right, Middle};
                 use events::MouseScrollDelta::LineDelta;
                 use events::{Touch, TouchPhase};
 
+                match xev.evtype {
+                    ffi::XI_ButtonPress | ffi::XI_ButtonRelease | ffi::XI_KeyPress | ffi::XI_KeyRelease
+                    | ffi::XI_TouchBegin | ffi::XI_TouchEnd => {
+                        let xev: &ffi::XIDeviceEvent = unsafe { &*(xev.data as *const _) };
+                        self.clipboard.set_time(xev.time);
+                    }
+                    _ => (),
+                }
+
                 match xev.evtype {
                     ffi::XI_ButtonPress | ffi::XI_ButtonRelease => {
                         let xev: &ffi::XIDeviceEvent = unsafe { &*(xev.data as *const _) };
@@ -716,7 +993,8 @@ impl EventsLoop {
                             // Suppress emulated scroll wheel clicks, since we handle the real motion events for those.
                             // In practice, even clicky scroll wheels appear to be reported by evdev (and XInput2 in
                             // turn) as axis motion, so we don't otherwise special-case these button presses.
-                            4 | 5 | 6 | 7 => if xev.flags & ffi::XIPointerEmulated == 0 {
+                            // Each click is a press and a release, and only the press scrolls.
+                            4 | 5 | 6 | 7 => if xev.flags & ffi::XIPointerEmulated == 0 && state == Pressed {
                                 callback(Event::WindowEvent {
                                     window_id,
                                     event: MouseWheel {
@@ -739,7 +1017,7 @@ impl EventsLoop {
                                 event: MouseInput {
                                     device_id,
                                     state,
-                                    button: Other(x as u8),
+                                    button: util::mouse_button(x),
                                     modifiers,
                                 },
                             }),
@@ -753,7 +1031,12 @@ impl EventsLoop {
 
                         let modifiers = ModifiersState::from(xev.mods);
 
+                        // A locked cursor is warped back as soon as it moves, and neither the motion
+                        // nor the warp is reported.
                         let cursor_moved = self.with_window(xev.event, |window| {
+                            if window.warp_to_cursor_lock(new_cursor_pos) {
+                                return false;
+                            }
                             let mut shared_state_lock = window.shared_state.lock();
                             util::maybe_change(&mut shared_state_lock.cursor_pos, new_cursor_pos)
                         });
@@ -790,27 +1073,28 @@ impl EventsLoop {
                                 Some(device) => device,
                                 None => return,
                             };
+                            // The scroll valuators of wheels that send button presses are moved by
+                            // the server too, and those presses are already reported.
+                            let emulated = xev.flags & ffi::XIPointerEmulated != 0;
 
                             let mut value = xev.valuators.values;
                             for i in 0..xev.valuators.mask_len*8 {
                                 if ffi::XIMaskIsSet(mask, i) {
                                     let x = unsafe { *value };
                                     if let Some(&mut (_, ref mut info)) = physical_device.scroll_axes.iter_mut().find(|&&mut (axis, _)| axis == i) {
-                                        let delta = (x - info.position) / info.increment;
-                                        info.position = x;
-                                        events.push(Event::WindowEvent {
-                                            window_id,
-                                            event: MouseWheel {
-                                                device_id,
-                                                delta: match info.orientation {
-                                                    ScrollOrientation::Horizontal => LineDelta(delta as f32, 0.0),
-                                                    // X11 vertical scroll coordinates are opposite to winit's
-                                                    ScrollOrientation::Vertical => LineDelta(0.0, -delta as f32),
+                                        if emulated {
+                                            info.position = x;
+                                        } else if let Some(delta) = info.scroll_to(x, physical_device.smooth_scroll) {
+                                            events.push(Event::WindowEvent {
+                                                window_id,
+                                                event: MouseWheel {
+                                                    device_id,
+                                                    delta,
+                                                    phase: TouchPhase::Moved,
+                                                    modifiers,
                                                 },
-                                                phase: TouchPhase::Moved,
-                                                modifiers,
-                                            },
-                                        });
+                                            });
+                                        }
                                     } else {
                                         events.push(Event::WindowEvent {
                                             window_id,
@@ -833,8 +1117,12 @@ impl EventsLoop {
                     ffi::XI_Enter => {
                         let xev: &ffi::XIEnterEvent = unsafe { &*(xev.data as *const _) };
 
+                        // The pointer only came back from the child window a grab is confined to.
+                        if xev.detail == ffi::NotifyInferior { return; }
+
                         let window_id = mkwid(xev.event);
                         let device_id = mkdid(xev.deviceid);
+                        self.pointer_window.set(Some(xev.event));
 
                         if let Some(all_info) = DeviceInfo::get(&self.xconn, ffi::XIAllDevices) {
                             let mut devices = self.devices.borrow_mut();
@@ -884,6 +1172,13 @@ impl EventsLoop {
                     ffi::XI_Leave => {
                         let xev: &ffi::XILeaveEvent = unsafe { &*(xev.data as *const _) };
 
+                        // The pointer only went into the child window a grab is confined to.
+                        if xev.detail == ffi::NotifyInferior { return; }
+
+                        if self.pointer_window.get() == Some(xev.event) {
+                            self.pointer_window.set(None);
+                        }
+
                         // Leave, FocusIn, and FocusOut can be received by a window that's already
                         // been destroyed, which the user presumably doesn't want to deal with.
                         let window_closed = !self.window_exists(xev.event);
@@ -912,6 +1207,8 @@ impl EventsLoop {
 
                         callback(Event::WindowEvent { window_id, event: Focused(true) });
 
+                        self.with_window(xev.event, |window| window.restore_cursor_grab());
+
                         // The deviceid for this event is for a keyboard instead of a pointer,
                         // so we have to do a little extra work.
                         let pointer_id = self.devices
@@ -975,6 +1272,75 @@ impl EventsLoop {
                         }
                     }
 
+                    ffi::XI_GesturePinchBegin | ffi::XI_GesturePinchUpdate | ffi::XI_GesturePinchEnd => {
+                        let xev: &ffi::XIGesturePinchEvent = unsafe { &*(xev.data as *const _) };
+                        let window_id = mkwid(xev.event);
+                        let device_id = mkdid(xev.deviceid);
+                        let phase = match xev.evtype {
+                            ffi::XI_GesturePinchBegin => TouchPhase::Started,
+                            ffi::XI_GesturePinchUpdate => TouchPhase::Moved,
+                            ffi::XI_GesturePinchEnd => if xev.flags & ffi::XIGesturePinchEventCancelled != 0 {
+                                TouchPhase::Cancelled
+                            } else {
+                                TouchPhase::Ended
+                            },
+                            _ => unreachable!()
+                        };
+                        // The scale is relative to the start of the gesture, so it's 1 when it starts.
+                        let delta = if phase == TouchPhase::Started {
+                            0.0
+                        } else {
+                            xev.scale - self.pinch_scale.get()
+                        };
+                        self.pinch_scale.set(xev.scale);
+                        callback(Event::WindowEvent {
+                            window_id,
+                            event: WindowEvent::PinchGesture { device_id, delta, phase },
+                        });
+                        callback(Event::WindowEvent {
+                            window_id,
+                            event: WindowEvent::RotationGesture {
+                                device_id,
+                                // X11 angles are clockwise, which is the opposite of winit's
+                                delta: -xev.delta_angle as f32,
+                                phase,
+                            },
+                        });
+                    }
+
+                    ffi::XI_GestureSwipeBegin | ffi::XI_GestureSwipeUpdate | ffi::XI_GestureSwipeEnd => {
+                        let xev: &ffi::XIGestureSwipeEvent = unsafe { &*(xev.data as *const _) };
+                        let window_id = mkwid(xev.event);
+                        let phase = match xev.evtype {
+                            ffi::XI_GestureSwipeBegin => TouchPhase::Started,
+                            ffi::XI_GestureSwipeUpdate => TouchPhase::Moved,
+                            ffi::XI_GestureSwipeEnd => if xev.flags & ffi::XIGestureSwipeEventCancelled != 0 {
+                                TouchPhase::Cancelled
+                            } else {
+                                TouchPhase::Ended
+                            },
+                            _ => unreachable!()
+                        };
+                        let dpi_factor = self.with_window(xev.event, |window| {
+                            window.get_hidpi_factor()
+                        });
+                        if let Some(dpi_factor) = dpi_factor {
+                            let delta = LogicalPosition::from_physical(
+                                (xev.delta_x, xev.delta_y),
+                                dpi_factor,
+                            );
+                            callback(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::SwipeGesture {
+                                    device_id: mkdid(xev.deviceid),
+                                    fingers: xev.detail as u32,
+                                    delta,
+                                    phase,
+                                },
+                            });
+                        }
+                    }
+
                     ffi::XI_RawButtonPress | ffi::XI_RawButtonRelease => {
                         let xev: &ffi::XIRawEvent = unsafe { &*(xev.data as *const _) };
                         if xev.flags & ffi::XIPointerEmulated == 0 {
@@ -1051,7 +1417,7 @@ impl EventsLoop {
                         };
                         self.xconn.check_errors().expect("Failed to lookup raw keysym");
 
-                        let virtual_keycode = events::keysym_to_element(keysym as c_uint);
+                        let virtual_keycode = keysym_to_vkey(keysym as u32);
 
                         callback(Event::DeviceEvent {
                             device_id: mkdid(device_id),
@@ -1132,9 +1498,18 @@ impl EventsLoop {
         }
 
         match self.ime_receiver.try_recv() {
-            Ok((window_id, x, y)) => {
+            Ok(ImeRequest::Position(window_id, x, y)) => {
                 self.ime.borrow_mut().send_xim_spot(window_id, x, y);
             },
+            Ok(ImeRequest::Area(window_id, x, y, width, height)) => {
+                self.ime.borrow_mut().send_xim_area(window_id, x, y, width, height);
+            },
+            Ok(ImeRequest::Allowed(window_id, allowed)) => {
+                let result = self.ime.borrow_mut().set_ime_allowed(window_id, allowed);
+                if let Err(err) = result {
+                    warn!("Failed to recreate input context: {:?}", err);
+                }
+            },
             Err(_) => (),
         }
     }
@@ -1328,6 +1703,8 @@ fn mkdid(w: c_int) -> ::DeviceId { ::DeviceId(::platform::DeviceId::X(DeviceId(w
 struct Device {
     name: String,
     scroll_axes: Vec<(i32, ScrollAxis)>,
+    // Whether the device scrolls smoothly, like a touchpad, rather than by the lines of a wheel.
+    smooth_scroll: bool,
     // For master devices, this is the paired device (pointer <-> keyboard).
     // For slave devices, this is the master.
     attachment: c_int,
@@ -1338,6 +1715,39 @@ struct ScrollAxis {
     increment: f64,
     orientation: ScrollOrientation,
     position: f64,
+    // The lines a wheel scrolled by that weren't reported yet, because they didn't add up to one.
+    remainder: f64,
+}
+
+// How many pixels an increment of smooth scrolling is reported as. This is what libinput counts an
+// increment as by default.
+const SCROLL_PIXELS_PER_INCREMENT: f64 = 15.0;
+
+impl ScrollAxis {
+    // Moves the axis to `position`, returning how far it scrolled if there's anything to report.
+    // Smooth scrolling is reported in pixels, and wheels in whole lines. High-resolution wheels
+    // move by fractions of an increment, which add up to lines.
+    fn scroll_to(&mut self, position: f64, smooth: bool) -> Option<MouseScrollDelta> {
+        let increments = (position - self.position) / self.increment;
+        self.position = position;
+        let (x, y) = match self.orientation {
+            ScrollOrientation::Horizontal => (1.0, 0.0),
+            // X11 vertical scroll coordinates are opposite to winit's
+            ScrollOrientation::Vertical => (0.0, -1.0),
+        };
+        if smooth {
+            let pixels = increments * SCROLL_PIXELS_PER_INCREMENT;
+            Some(MouseScrollDelta::PixelDelta((x * pixels, y * pixels).into()))
+        } else {
+            self.remainder += increments;
+            let lines = self.remainder.trunc();
+            if lines == 0.0 {
+                return None;
+            }
+            self.remainder -= lines;
+            Some(MouseScrollDelta::LineDelta((x * lines) as f32, (y * lines) as f32))
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -1350,6 +1760,7 @@ impl Device {
     fn new(el: &EventsLoop, info: &ffi::XIDeviceInfo) -> Self {
         let name = unsafe { CStr::from_ptr(info.name).to_string_lossy() };
         let mut scroll_axes = Vec::new();
+        let mut smooth_scroll = false;
 
         if Device::physical_device(info) {
             // Register for global raw events
@@ -1375,22 +1786,35 @@ impl Device {
                                 _ => { unreachable!() }
                             },
                             position: 0.0,
+                            remainder: 0.0,
                         }));
                     }
+                    ffi::XITouchClass => {
+                        let info = unsafe { mem::transmute::<&ffi::XIAnyClassInfo, &ffi::XITouchClassInfo>(class) };
+                        if info.mode == ffi::XIDependentTouch {
+                            smooth_scroll = true;
+                        }
+                    }
                     _ => {}
                 }
             }
         }
 
+        if Device::physical_device(info) && !smooth_scroll {
+            smooth_scroll = Device::scrolls_with_fingers(el, info.deviceid);
+        }
+
         let mut device = Device {
             name: name.into_owned(),
             scroll_axes: scroll_axes,
+            smooth_scroll: smooth_scroll,
             attachment: info.attachment,
         };
         device.reset_scroll_position(info);
         device
     }
 
+    // Only the position is reset, so the remainder of a wheel is still reported once it adds up.
     fn reset_scroll_position(&mut self, info: &ffi::XIDeviceInfo) {
         if Device::physical_device(info) {
             for class_ptr in Device::classes(info) {
@@ -1408,6 +1832,18 @@ impl Device {
         }
     }
 
+    // Touchpads don't always have a touch class, but their drivers let them scroll with two fingers or
+    // along an edge, which mice and their wheels can't.
+    fn scrolls_with_fingers(el: &EventsLoop, device_id: c_int) -> bool {
+        // Two-finger, edge and button scrolling, in that order.
+        let libinput = unsafe { el.xconn.get_atom_unchecked(b"libinput Scroll Methods Available\0") };
+        if let Some(methods) = el.xconn.get_device_property_u8(device_id, libinput) {
+            return methods.iter().take(2).any(|&method| method != 0);
+        }
+        let synaptics = unsafe { el.xconn.get_atom_unchecked(b"Synaptics Edge Scrolling\0") };
+        el.xconn.get_device_property_u8(device_id, synaptics).is_some()
+    }
+
     #[inline]
     fn physical_device(info: &ffi::XIDeviceInfo) -> bool {
         info._use == ffi::XISlaveKeyboard || info._use == ffi::XISlavePointer || info._use == ffi::XIFloatingSlave
@@ -1,7 +1,7 @@
-use std::str;
+use std::{cmp, slice, str};
 
 use super::*;
-use events::ModifiersState;
+use events::{ModifiersState, MouseButton};
 
 pub const VIRTUAL_CORE_POINTER: c_int = 2;
 pub const VIRTUAL_CORE_KEYBOARD: c_int = 3;
@@ -29,18 +29,45 @@ pub struct PointerState<'a> {
     child: ffi::Window,
     pub root_x: c_double,
     pub root_y: c_double,
-    win_x: c_double,
-    win_y: c_double,
+    pub win_x: c_double,
+    pub win_y: c_double,
     buttons: ffi::XIButtonState,
     modifiers: ffi::XIModifierState,
     group: ffi::XIGroupState,
-    relative_to_window: bool,
+    pub relative_to_window: bool,
 }
 
 impl<'a> PointerState<'a> {
     pub fn get_modifier_state(&self) -> ModifiersState {
         self.modifiers.into()
     }
+
+    pub fn get_pressed_buttons(&self) -> Vec<MouseButton> {
+        if self.buttons.mask.is_null() {
+            return Vec::new();
+        }
+        let mask = unsafe { slice::from_raw_parts(self.buttons.mask, self.buttons.mask_len as usize) };
+        (0..self.buttons.mask_len * 8)
+            .filter(|&button| ffi::XIMaskIsSet(mask, button))
+            .filter_map(|button| match button as c_uint {
+                // Buttons 4 to 7 are scroll wheels, which are never held.
+                0 | 4 | 5 | 6 | 7 => None,
+                button => Some(mouse_button(button)),
+            })
+            .collect()
+    }
+}
+
+// Maps the number of a button that isn't a scroll wheel.
+pub fn mouse_button(button: c_uint) -> MouseButton {
+    match button {
+        ffi::Button1 => MouseButton::Left,
+        ffi::Button2 => MouseButton::Middle,
+        ffi::Button3 => MouseButton::Right,
+        8 => MouseButton::Back,
+        9 => MouseButton::Forward,
+        button => MouseButton::Other(button as u8),
+    }
 }
 
 impl<'a> Drop for PointerState<'a> {
@@ -72,6 +99,39 @@ impl XConnection {
         Flusher::new(self)
     }
 
+    // Selects `events` along with the ones in `mask`, for events past the 32 that fit in it.
+    pub fn select_xinput_events_with(
+        &self,
+        window: c_ulong,
+        device_id: c_int,
+        mask: i32,
+        events: &[c_int],
+    ) -> Flusher {
+        let mut bytes = [0 as c_uchar; 8];
+        for i in 0..4 {
+            bytes[i] = (mask >> (i * 8)) as c_uchar;
+        }
+        let mut mask_len = 4;
+        for &event in events {
+            ffi::XISetMask(&mut bytes, event);
+            mask_len = cmp::max(mask_len, (event >> 3) + 1);
+        }
+        let mut event_mask = ffi::XIEventMask {
+            deviceid: device_id,
+            mask: bytes.as_mut_ptr(),
+            mask_len,
+        };
+        unsafe {
+            (self.xinput2.XISelectEvents)(
+                self.display,
+                window,
+                &mut event_mask as *mut ffi::XIEventMask,
+                1, // number of masks to read from pointer above
+            );
+        }
+        Flusher::new(self)
+    }
+
     #[allow(dead_code)]
     pub fn select_xkb_events(&self, device_id: c_uint, mask: c_ulong) -> Option<Flusher> {
         let status = unsafe {
@@ -117,6 +177,43 @@ impl XConnection {
         }
     }
 
+    // Gets an 8-bit property of an input device, or `None` if its driver didn't set it.
+    pub fn get_device_property_u8(&self, device_id: c_int, property: ffi::Atom) -> Option<Vec<u8>> {
+        unsafe {
+            let mut actual_type = 0;
+            let mut actual_format = 0;
+            let mut count = 0;
+            let mut bytes_after = 0;
+            let mut data = ptr::null_mut();
+            let status = (self.xinput2.XIGetProperty)(
+                self.display,
+                device_id,
+                property,
+                0,
+                // Longer than any of the properties we read.
+                16,
+                ffi::False,
+                ffi::AnyPropertyType as c_ulong,
+                &mut actual_type,
+                &mut actual_format,
+                &mut count,
+                &mut bytes_after,
+                &mut data,
+            );
+            // The device may have been removed in the meantime.
+            let failed = self.check_errors().is_err() || status != ffi::Success as c_int;
+            let value = if !failed && actual_type != 0 && actual_format == 8 && !data.is_null() {
+                Some(slice::from_raw_parts(data, count as usize).to_vec())
+            } else {
+                None
+            };
+            if !data.is_null() {
+                (self.xlib.XFree)(data as *mut c_void);
+            }
+            value
+        }
+    }
+
     fn lookup_utf8_inner(
         &self,
         ic: ffi::XIC,
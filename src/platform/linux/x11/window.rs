@@ -1,5 +1,5 @@
-use std::{cmp, env, mem};
-use std::ffi::CString;
+use std::{cmp, env, mem, ptr, slice};
+use std::ffi::{CStr, CString};
 use std::os::raw::*;
 use std::path::Path;
 use std::sync::Arc;
@@ -7,7 +7,7 @@ use std::sync::Arc;
 use libc;
 use parking_lot::Mutex;
 
-use {Icon, MouseCursor, WindowAttributes};
+use {CursorGrabMode, CustomCursor, DndAction, Icon, MouseCursor, WindowAttributes};
 use CreationError::{self, OsError};
 use dpi::{LogicalPosition, LogicalSize};
 use platform::MonitorId as PlatformMonitorId;
@@ -15,7 +15,10 @@ use platform::PlatformSpecificWindowBuilderAttributes;
 use platform::x11::MonitorId as X11MonitorId;
 use window::MonitorId as RootMonitorId;
 
-use super::{ffi, util, ImeSender, XConnection, XError, WindowId, EventsLoop};
+use super::{ffi, util, Clipboard, DragSource, ImeSender, ImeRequest, XConnection, XError, WindowId, EventsLoop};
+use super::super::clipboard::ClipboardContents;
+use super::super::cursor::{theme_names, Cursor, CursorImage, ThemeOverride};
+use super::super::dnd::DropResponse;
 
 unsafe extern "C" fn visibility_predicate(
     _display: *mut ffi::Display,
@@ -60,10 +63,22 @@ pub struct UnownedWindow {
     xwindow: ffi::Window, // never changes
     root: ffi::Window, // never changes
     screen_id: i32, // never changes
-    cursor: Mutex<MouseCursor>,
-    cursor_grabbed: Mutex<bool>,
+    cursor: Mutex<Cursor>,
+    // The mode set with `set_cursor_grab`, which applies while there's no confine area.
+    cursor_grab: Mutex<CursorGrabMode>,
+    // The area of the window the cursor is confined to with `confine_cursor_to`, if any.
+    confine_area: Mutex<Option<(LogicalPosition, LogicalSize)>>,
+    // An `InputOnly` child window covering the confine area, for the grab to be confined to.
+    confine_window: Mutex<Option<ffi::Window>>,
+    // Where a locked cursor is kept, in physical window coordinates.
+    cursor_lock: Mutex<Option<(i32, i32)>>,
     cursor_hidden: Mutex<bool>,
     ime_sender: Mutex<ImeSender>,
+    clipboard: Arc<Clipboard>, // never changes
+    drag_source: Arc<DragSource>, // never changes
+    cursor_theme: Arc<Mutex<ThemeOverride>>, // never changes
+    pub accepted_drop_types: Mutex<Vec<ffi::Atom>>,
+    pub drop_response: Mutex<DropResponse>,
     pub multitouch: bool, // never changes
     pub shared_state: Mutex<SharedState>,
 }
@@ -198,9 +213,17 @@ impl UnownedWindow {
             root,
             screen_id,
             cursor: Default::default(),
-            cursor_grabbed: Default::default(),
+            cursor_grab: Default::default(),
+            confine_area: Default::default(),
+            confine_window: Default::default(),
+            cursor_lock: Default::default(),
             cursor_hidden: Default::default(),
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
+            clipboard: Arc::clone(&event_loop.clipboard),
+            drag_source: Arc::clone(&event_loop.drag_source),
+            cursor_theme: Arc::clone(&event_loop.cursor_theme),
+            accepted_drop_types: Default::default(),
+            drop_response: Default::default(),
             multitouch: window_attrs.multitouch,
             shared_state: SharedState::new(dpi_factor),
         };
@@ -351,12 +374,25 @@ impl UnownedWindow {
                 }
                 mask
             };
-            xconn.select_xinput_events(window.xwindow, ffi::XIAllMasterDevices, mask).queue();
+            if event_loop.xi2_gestures {
+                let gestures = [
+                    ffi::XI_GesturePinchBegin,
+                    ffi::XI_GesturePinchUpdate,
+                    ffi::XI_GesturePinchEnd,
+                    ffi::XI_GestureSwipeBegin,
+                    ffi::XI_GestureSwipeUpdate,
+                    ffi::XI_GestureSwipeEnd,
+                ];
+                xconn.select_xinput_events_with(window.xwindow, ffi::XIAllMasterDevices, mask, &gestures)
+                    .queue();
+            } else {
+                xconn.select_xinput_events(window.xwindow, ffi::XIAllMasterDevices, mask).queue();
+            }
 
             {
                 let result = event_loop.ime
                     .borrow_mut()
-                    .create_context(window.xwindow);
+                    .create_context(window.xwindow, true);
                 if let Err(err) = result {
                     return Err(OsError(format!("Failed to create input context: {:?}", err)));
                 }
@@ -457,6 +493,67 @@ impl UnownedWindow {
         )
     }
 
+    #[inline]
+    pub fn set_clipboard_text(&self, text: &str) -> Result<(), String> {
+        self.clipboard.set_text(self.clipboard.atoms.clipboard, text)
+    }
+
+    #[inline]
+    pub fn get_clipboard_text(&self) -> Option<String> {
+        self.clipboard.get_text(self.clipboard.atoms.clipboard)
+    }
+
+    #[inline]
+    pub fn set_clipboard_contents(&self, contents: ClipboardContents) -> Result<(), String> {
+        self.clipboard.set_contents(self.clipboard.atoms.clipboard, contents)
+    }
+
+    #[inline]
+    pub fn get_clipboard_mime_types(&self) -> Vec<String> {
+        self.clipboard.get_mime_types(self.clipboard.atoms.clipboard)
+    }
+
+    #[inline]
+    pub fn get_clipboard_data(&self, mime_type: &str) -> Option<Vec<u8>> {
+        self.clipboard.get_data(self.clipboard.atoms.clipboard, mime_type)
+    }
+
+    #[inline]
+    pub fn set_primary_selection_text(&self, text: &str) -> Result<(), String> {
+        self.clipboard.set_text(ffi::XA_PRIMARY, text)
+    }
+
+    #[inline]
+    pub fn get_primary_selection_text(&self) -> Option<String> {
+        self.clipboard.get_text(ffi::XA_PRIMARY)
+    }
+
+    pub fn start_drag(&self, contents: ClipboardContents, actions: &[DndAction]) -> Result<(), String> {
+        let types = contents
+            .iter()
+            .filter_map(|&(ref mime_type, _)| CString::new(mime_type.as_str()).ok())
+            .map(|mime_type| self.xconn.get_atom(mime_type))
+            .collect();
+        // The clipboard answers the drop target's requests for the data.
+        self.clipboard.set_contents(self.drag_source.atoms.selection, contents)?;
+        self.drag_source.start(self.xwindow, self.root, types, actions)
+    }
+
+    #[inline]
+    pub fn set_accepted_drop_types(&self, mime_types: &[&str]) {
+        *self.accepted_drop_types.lock() = mime_types
+            .iter()
+            .filter_map(|&mime_type| CString::new(mime_type).ok())
+            .map(|mime_type| self.xconn.get_atom(mime_type))
+            .collect();
+    }
+
+    #[inline]
+    pub fn set_drop_response(&self, action: Option<DndAction>, area: Option<(LogicalPosition, LogicalSize)>) {
+        // This is sent with the answer to the next XdndPosition.
+        *self.drop_response.lock() = DropResponse { action, area };
+    }
+
     #[inline]
     pub fn set_urgent(&self, is_urgent: bool) {
         let mut wm_hints = self.xconn.get_wm_hints(self.xwindow).expect("`XGetWMHints` failed");
@@ -885,6 +982,8 @@ impl UnownedWindow {
                 new_height.round() as c_uint,
             );
         }
+        self.update_cursor_for_dpi(new_dpi_factor);
+        self.update_confine_area_for_dpi(new_dpi_factor);
         (new_width, new_height, util::Flusher::new(&self.xconn))
     }
 
@@ -945,18 +1044,58 @@ impl UnownedWindow {
         }
     }
 
-    fn load_cursor(&self, name: &[u8]) -> ffi::Cursor {
+    // The cursor size set with `set_cursor_theme`, `XCURSOR_SIZE` or the `Xcursor.size` resource,
+    // in logical pixels.
+    fn explicit_cursor_size(&self, theme: &ThemeOverride) -> Option<c_int> {
+        theme.size
+            .map(|size| size as c_int)
+            .or_else(|| env::var("XCURSOR_SIZE").ok().and_then(|size| size.parse().ok()))
+            .or_else(|| unsafe {
+                let size = (self.xconn.xlib.XGetDefault)(
+                    self.xconn.display,
+                    b"Xcursor\0".as_ptr() as *const c_char,
+                    b"size\0".as_ptr() as *const c_char,
+                );
+                if size.is_null() {
+                    None
+                } else {
+                    // Owned by Xlib.
+                    CStr::from_ptr(size).to_str().ok().and_then(|size| size.parse().ok())
+                }
+            })
+    }
+
+    // Loads the cursor from the theme. Unless they're overridden, the theme and size come from
+    // Xcursor, which reads them from `XCURSOR_THEME` and `XCURSOR_SIZE` or the `Xcursor.theme` and
+    // `Xcursor.size` resources. Only those sizes are scaled for the DPI factor, as the size Xcursor
+    // falls back to already accounts for `Xft.dpi`.
+    fn load_cursor(&self, name: &str, dpi_factor: f64) -> ffi::Cursor {
+        let name = CString::new(name).expect("Cursor name contained null byte");
+        let theme = self.cursor_theme.lock().clone();
+        let theme_name = theme.name.and_then(|theme_name| CString::new(theme_name).ok());
         unsafe {
-            (self.xconn.xcursor.XcursorLibraryLoadCursor)(
-                self.xconn.display,
-                name.as_ptr() as *const c_char,
-            )
+            let theme_name = match theme_name {
+                Some(ref theme_name) => theme_name.as_ptr(),
+                None => (self.xconn.xcursor.XcursorGetTheme)(self.xconn.display) as *const c_char,
+            };
+            let size = match self.explicit_cursor_size(&theme) {
+                Some(size) if size > 0 => (size as f64 * dpi_factor).round() as c_int,
+                _ => (self.xconn.xcursor.XcursorGetDefaultSize)(self.xconn.display),
+            };
+            let ximages = (self.xconn.xcursor.XcursorLibraryLoadImages)(name.as_ptr(), theme_name, size);
+            if ximages.is_null() {
+                // Falls back to the core cursor font for the names it has.
+                return (self.xconn.xcursor.XcursorLibraryLoadCursor)(self.xconn.display, name.as_ptr());
+            }
+            let cursor = (self.xconn.xcursor.XcursorImagesLoadCursor)(self.xconn.display, ximages);
+            (self.xconn.xcursor.XcursorImagesDestroy)(ximages);
+            cursor
         }
     }
 
-    fn load_first_existing_cursor(&self, names: &[&[u8]]) -> ffi::Cursor {
+    fn load_first_existing_cursor(&self, names: &[&str], dpi_factor: f64) -> ffi::Cursor {
         for name in names.iter() {
-            let xcursor = self.load_cursor(name);
+            let xcursor = self.load_cursor(name, dpi_factor);
             if xcursor != 0 {
                 return xcursor;
             }
@@ -964,62 +1103,46 @@ impl UnownedWindow {
         0
     }
 
-    fn get_cursor(&self, cursor: MouseCursor) -> ffi::Cursor {
-        let load = |name: &[u8]| {
-            self.load_cursor(name)
-        };
+    fn get_cursor(&self, cursor: MouseCursor, dpi_factor: f64) -> ffi::Cursor {
+        self.load_first_existing_cursor(theme_names(cursor), dpi_factor)
+    }
 
-        let loadn = |names: &[&[u8]]| {
-            self.load_first_existing_cursor(names)
-        };
+    fn load_custom_cursor(&self, cursor: &CustomCursor, dpi_factor: f64) -> ffi::Cursor {
+        unsafe {
+            let ximages = (self.xconn.xcursor.XcursorImagesCreate)(cursor.frames.len() as c_int);
+            if ximages.is_null() {
+                return 0;
+            }
+            for frame in &cursor.frames {
+                let image = CursorImage::new(frame, dpi_factor);
+                let ximage = (self.xconn.xcursor.XcursorImageCreate)(image.width as c_int, image.height as c_int);
+                if ximage.is_null() {
+                    (self.xconn.xcursor.XcursorImagesDestroy)(ximages);
+                    return 0;
+                }
+                (*ximage).xhot = image.hotspot_x;
+                (*ximage).yhot = image.hotspot_y;
+                (*ximage).delay = (frame.delay.as_secs() * 1000) as c_uint
+                    + frame.delay.subsec_nanos() / 1_000_000;
+                let pixels = slice::from_raw_parts_mut((*ximage).pixels, image.pixels.len());
+                for (dst, &src) in pixels.iter_mut().zip(image.pixels.iter()) {
+                    *dst = src as ffi::XcursorPixel;
+                }
+                // The images are freed along with `ximages`.
+                *(*ximages).images.offset((*ximages).nimage as isize) = ximage;
+                (*ximages).nimage += 1;
+            }
+            // Xcursor animates cursors with several images on its own, using their delays.
+            let cursor = (self.xconn.xcursor.XcursorImagesLoadCursor)(self.xconn.display, ximages);
+            (self.xconn.xcursor.XcursorImagesDestroy)(ximages);
+            cursor
+        }
+    }
 
-        // Try multiple names in some cases where the name
-        // differs on the desktop environments or themes.
-        //
-        // Try the better looking (or more suiting) names first.
-        match cursor {
-            MouseCursor::Alias => load(b"link\0"),
-            MouseCursor::Arrow => load(b"arrow\0"),
-            MouseCursor::Cell => load(b"plus\0"),
-            MouseCursor::Copy => load(b"copy\0"),
-            MouseCursor::Crosshair => load(b"crosshair\0"),
-            MouseCursor::Default => load(b"left_ptr\0"),
-            MouseCursor::Hand => loadn(&[b"hand2\0", b"hand1\0"]),
-            MouseCursor::Help => load(b"question_arrow\0"),
-            MouseCursor::Move => load(b"move\0"),
-            MouseCursor::Grab => loadn(&[b"openhand\0", b"grab\0"]),
-            MouseCursor::Grabbing => loadn(&[b"closedhand\0", b"grabbing\0"]),
-            MouseCursor::Progress => load(b"left_ptr_watch\0"),
-            MouseCursor::AllScroll => load(b"all-scroll\0"),
-            MouseCursor::ContextMenu => load(b"context-menu\0"),
-
-            MouseCursor::NoDrop => loadn(&[b"no-drop\0", b"circle\0"]),
-            MouseCursor::NotAllowed => load(b"crossed_circle\0"),
-
-
-            // Resize cursors
-            MouseCursor::EResize => load(b"right_side\0"),
-            MouseCursor::NResize => load(b"top_side\0"),
-            MouseCursor::NeResize => load(b"top_right_corner\0"),
-            MouseCursor::NwResize => load(b"top_left_corner\0"),
-            MouseCursor::SResize => load(b"bottom_side\0"),
-            MouseCursor::SeResize => load(b"bottom_right_corner\0"),
-            MouseCursor::SwResize => load(b"bottom_left_corner\0"),
-            MouseCursor::WResize => load(b"left_side\0"),
-            MouseCursor::EwResize => load(b"h_double_arrow\0"),
-            MouseCursor::NsResize => load(b"v_double_arrow\0"),
-            MouseCursor::NwseResize => loadn(&[b"bd_double_arrow\0", b"size_bdiag\0"]),
-            MouseCursor::NeswResize => loadn(&[b"fd_double_arrow\0", b"size_fdiag\0"]),
-            MouseCursor::ColResize => loadn(&[b"split_h\0", b"h_double_arrow\0"]),
-            MouseCursor::RowResize => loadn(&[b"split_v\0", b"v_double_arrow\0"]),
-
-            MouseCursor::Text => loadn(&[b"text\0", b"xterm\0"]),
-            MouseCursor::VerticalText => load(b"vertical-text\0"),
-
-            MouseCursor::Wait => load(b"watch\0"),
-
-            MouseCursor::ZoomIn => load(b"zoom-in\0"),
-            MouseCursor::ZoomOut => load(b"zoom-out\0"),
+    fn load_current_cursor(&self, dpi_factor: f64) -> ffi::Cursor {
+        match *self.cursor.lock() {
+            Cursor::Named(cursor) => self.get_cursor(cursor, dpi_factor),
+            Cursor::Custom(ref cursor) => self.load_custom_cursor(cursor, dpi_factor),
         }
     }
 
@@ -1035,12 +1158,35 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_cursor(&self, cursor: MouseCursor) {
-        *self.cursor.lock() = cursor;
+        *self.cursor.lock() = Cursor::Named(cursor);
         if !*self.cursor_hidden.lock() {
-            self.update_cursor(self.get_cursor(cursor));
+            self.update_cursor(self.get_cursor(cursor, self.get_hidpi_factor()));
         }
     }
 
+    #[inline]
+    pub fn set_custom_cursor(&self, cursor: CustomCursor) {
+        let xcursor = self.load_custom_cursor(&cursor, self.get_hidpi_factor());
+        *self.cursor.lock() = Cursor::Custom(cursor);
+        if !*self.cursor_hidden.lock() {
+            self.update_cursor(xcursor);
+        } else if xcursor != 0 {
+            unsafe { (self.xconn.xlib.XFreeCursor)(self.xconn.display, xcursor) };
+        }
+    }
+
+    // Cursors are scaled for the DPI factor, so they have to be reloaded when it changes.
+    fn update_cursor_for_dpi(&self, dpi_factor: f64) {
+        if !*self.cursor_hidden.lock() {
+            self.update_cursor(self.load_current_cursor(dpi_factor));
+        }
+    }
+
+    // Loads the cursor again after the cursor theme changed.
+    pub fn reload_cursor(&self) {
+        self.update_cursor_for_dpi(self.get_hidpi_factor());
+    }
+
     // TODO: This could maybe be cached. I don't think it's worth
     // the complexity, since cursor changes are not so common,
     // and this is just allocating a 1x1 pixmap...
@@ -1080,61 +1226,179 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
-        let mut grabbed_lock = self.cursor_grabbed.lock();
-        if grab == *grabbed_lock { return Ok(()); }
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), String> {
+        self.set_cursor_grab_inner(mode, None)
+    }
+
+    #[inline]
+    pub fn confine_cursor_to(&self, area: Option<(LogicalPosition, LogicalSize)>) -> Result<(), String> {
+        // Without an area, the cursor goes back to the grab `set_cursor_grab` asked for.
+        let mode = *self.cursor_grab.lock();
+        self.set_cursor_grab_inner(mode, area)
+    }
+
+    fn set_cursor_grab_inner(
+        &self,
+        mode: CursorGrabMode,
+        area: Option<(LogicalPosition, LogicalSize)>,
+    ) -> Result<(), String> {
+        let mut grab_lock = self.cursor_grab.lock();
+        let mut area_lock = self.confine_area.lock();
+        if mode == *grab_lock && area == *area_lock { return Ok(()); }
         unsafe {
             // We ungrab before grabbing to prevent passive grabs from causing `AlreadyGrabbed`.
-            // Therefore, this is common to both codepaths.
+            // Therefore, this is common to all codepaths.
             (self.xconn.xlib.XUngrabPointer)(self.xconn.display, ffi::CurrentTime);
         }
-        let result = if grab {
-            let result = unsafe {
-                (self.xconn.xlib.XGrabPointer)(
-                    self.xconn.display,
-                    self.xwindow,
-                    ffi::True,
-                    (
-                        ffi::ButtonPressMask
-                        | ffi::ButtonReleaseMask
-                        | ffi::EnterWindowMask
-                        | ffi::LeaveWindowMask
-                        | ffi::PointerMotionMask
-                        | ffi::PointerMotionHintMask
-                        | ffi::Button1MotionMask
-                        | ffi::Button2MotionMask
-                        | ffi::Button3MotionMask
-                        | ffi::Button4MotionMask
-                        | ffi::Button5MotionMask
-                        | ffi::ButtonMotionMask
-                        | ffi::KeymapStateMask
-                    ) as c_uint,
-                    ffi::GrabModeAsync,
-                    ffi::GrabModeAsync,
-                    self.xwindow,
-                    0,
-                    ffi::CurrentTime,
-                )
-            };
-
-            match result {
-                ffi::GrabSuccess => Ok(()),
-                ffi::AlreadyGrabbed => Err("Cursor could not be grabbed: already grabbed by another client"),
-                ffi::GrabInvalidTime => Err("Cursor could not be grabbed: invalid time"),
-                ffi::GrabNotViewable => Err("Cursor could not be grabbed: grab location not viewable"),
-                ffi::GrabFrozen => Err("Cursor could not be grabbed: frozen by another client"),
-                _ => unreachable!(),
-            }.map_err(|err| err.to_owned())
-        } else {
-            self.xconn.flush_requests()
-                .map_err(|err| format!("Failed to call `XUngrabPointer`: {:?}", err))
+        *self.cursor_lock.lock() = None;
+        if let (None, Some(confine_window)) = (area, *self.confine_window.lock()) {
+            unsafe {
+                (self.xconn.xlib.XUnmapWindow)(self.xconn.display, confine_window);
+            }
+        }
+        let result = match (mode, area) {
+            // The area takes precedence over the mode until it's cleared.
+            (_, Some(area)) => self.grab_pointer(Some(area)),
+            (CursorGrabMode::None, None) => self.xconn.flush_requests()
+                .map_err(|err| format!("Failed to call `XUngrabPointer`: {:?}", err)),
+            (CursorGrabMode::Confined, None) => self.grab_pointer(None),
+            // The pointer is still grabbed, so it can't get away from the window between two warps.
+            (CursorGrabMode::Locked, None) => self.grab_pointer(None).and_then(|()| self.lock_cursor()),
         };
         if result.is_ok() {
-            *grabbed_lock = grab;
+            *grab_lock = mode;
+            *area_lock = area;
         }
         result
     }
 
+    // Grabs the pointer again when the window gets the focus back, in case the grab was broken
+    // in the meantime, e.g. by the window being unmapped.
+    pub fn restore_cursor_grab(&self) {
+        let mode = *self.cursor_grab.lock();
+        let area = *self.confine_area.lock();
+        if mode != CursorGrabMode::None || area.is_some() {
+            unsafe {
+                (self.xconn.xlib.XUngrabPointer)(self.xconn.display, ffi::CurrentTime);
+            }
+            let _ = self.grab_pointer(area);
+        }
+    }
+
+    fn grab_pointer(&self, area: Option<(LogicalPosition, LogicalSize)>) -> Result<(), String> {
+        let confine_to = match area {
+            Some(area) => self.update_confine_window(area, self.get_hidpi_factor()),
+            None => self.xwindow,
+        };
+        let result = unsafe {
+            (self.xconn.xlib.XGrabPointer)(
+                self.xconn.display,
+                self.xwindow,
+                ffi::True,
+                (
+                    ffi::ButtonPressMask
+                    | ffi::ButtonReleaseMask
+                    | ffi::EnterWindowMask
+                    | ffi::LeaveWindowMask
+                    | ffi::PointerMotionMask
+                    | ffi::PointerMotionHintMask
+                    | ffi::Button1MotionMask
+                    | ffi::Button2MotionMask
+                    | ffi::Button3MotionMask
+                    | ffi::Button4MotionMask
+                    | ffi::Button5MotionMask
+                    | ffi::ButtonMotionMask
+                    | ffi::KeymapStateMask
+                ) as c_uint,
+                ffi::GrabModeAsync,
+                ffi::GrabModeAsync,
+                confine_to,
+                0,
+                ffi::CurrentTime,
+            )
+        };
+
+        match result {
+            ffi::GrabSuccess => Ok(()),
+            ffi::AlreadyGrabbed => Err("Cursor could not be grabbed: already grabbed by another client"),
+            ffi::GrabInvalidTime => Err("Cursor could not be grabbed: invalid time"),
+            ffi::GrabNotViewable => Err("Cursor could not be grabbed: grab location not viewable"),
+            ffi::GrabFrozen => Err("Cursor could not be grabbed: frozen by another client"),
+            _ => unreachable!(),
+        }.map_err(|err| err.to_owned())
+    }
+
+    // Moves the child window the grab is confined to over the area, creating it the first time.
+    fn update_confine_window(
+        &self,
+        (position, size): (LogicalPosition, LogicalSize),
+        dpi_factor: f64,
+    ) -> ffi::Window {
+        let (x, y) = position.to_physical(dpi_factor).into();
+        let (width, height): (u32, u32) = size.to_physical(dpi_factor).into();
+        let (width, height) = (cmp::max(width, 1), cmp::max(height, 1));
+        let mut confine_window_lock = self.confine_window.lock();
+        let confine_window = *confine_window_lock.get_or_insert_with(|| unsafe {
+            // It's destroyed along with its parent.
+            let confine_window = (self.xconn.xlib.XCreateWindow)(
+                self.xconn.display,
+                self.xwindow,
+                x,
+                y,
+                width,
+                height,
+                0,
+                0,
+                ffi::InputOnly as c_uint,
+                ffi::CopyFromParent as *mut ffi::Visual,
+                0,
+                ptr::null_mut(),
+            )
+        });
+        unsafe {
+            (self.xconn.xlib.XMoveResizeWindow)(
+                self.xconn.display,
+                confine_window,
+                x,
+                y,
+                width,
+                height,
+            );
+            (self.xconn.xlib.XMapWindow)(self.xconn.display, confine_window);
+        }
+        confine_window
+    }
+
+    // The confine area is in logical coordinates, so its window has to follow DPI changes.
+    fn update_confine_area_for_dpi(&self, dpi_factor: f64) {
+        let area = *self.confine_area.lock();
+        if let Some(area) = area {
+            self.update_confine_window(area, dpi_factor);
+        }
+    }
+
+    // Locks the cursor where it is, which the grab has just confined to the window.
+    fn lock_cursor(&self) -> Result<(), String> {
+        let pointer = self.xconn.query_pointer(self.xwindow, util::VIRTUAL_CORE_POINTER)
+            .map_err(|err| format!("Failed to query the cursor position: {:?}", err))?;
+        *self.cursor_lock.lock() = Some((pointer.win_x as i32, pointer.win_y as i32));
+        Ok(())
+    }
+
+    // Warps a locked cursor back to where it was locked if it moved, and returns whether it's locked.
+    pub fn warp_to_cursor_lock(&self, position: (f64, f64)) -> bool {
+        let lock = *self.cursor_lock.lock();
+        match lock {
+            Some((x, y)) => {
+                if (position.0 as i32, position.1 as i32) != (x, y) {
+                    let _ = self.set_cursor_position_physical(x, y);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
     #[inline]
     pub fn hide_cursor(&self, hide: bool) {
         let mut hidden_lock = self.cursor_hidden.lock();
@@ -1142,7 +1406,7 @@ impl UnownedWindow {
         let cursor = if hide {
             self.create_empty_cursor().expect("Failed to create empty cursor")
         } else {
-            self.get_cursor(*self.cursor.lock())
+            self.load_current_cursor(self.get_hidpi_factor())
         };
         *hidden_lock = hide;
         drop(hidden_lock);
@@ -1171,6 +1435,22 @@ impl UnownedWindow {
         }
     }
 
+    // Returns where the cursor is relative to the window, or `None` if it's on another screen.
+    pub fn get_cursor_position_physical(&self) -> Option<(f64, f64)> {
+        let pointer = self.xconn.query_pointer(self.xwindow, util::VIRTUAL_CORE_POINTER).ok()?;
+        if pointer.relative_to_window {
+            Some((pointer.win_x, pointer.win_y))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn get_cursor_position(&self) -> Option<LogicalPosition> {
+        self.get_cursor_position_physical()
+            .map(|position| LogicalPosition::from_physical(position, self.get_hidpi_factor()))
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, logical_position: LogicalPosition) -> Result<(), String> {
         let (x, y) = logical_position.to_physical(self.get_hidpi_factor()).into();
@@ -1180,7 +1460,7 @@ impl UnownedWindow {
     pub(crate) fn set_ime_spot_physical(&self, x: i32, y: i32) {
         let _ = self.ime_sender
             .lock()
-            .send((self.xwindow, x as i16, y as i16));
+            .send(ImeRequest::Position(self.xwindow, x as i16, y as i16));
     }
 
     #[inline]
@@ -1189,6 +1469,27 @@ impl UnownedWindow {
         self.set_ime_spot_physical(x, y);
     }
 
+    pub(crate) fn set_ime_cursor_area_physical(&self, x: i32, y: i32, width: u32, height: u32) {
+        let _ = self.ime_sender
+            .lock()
+            .send(ImeRequest::Area(self.xwindow, x as i16, y as i16, width as u16, height as u16));
+    }
+
+    #[inline]
+    pub fn set_ime_cursor_area(&self, logical_position: LogicalPosition, logical_size: LogicalSize) {
+        let dpi_factor = self.get_hidpi_factor();
+        let (x, y) = logical_position.to_physical(dpi_factor).into();
+        let (width, height) = logical_size.to_physical(dpi_factor).into();
+        self.set_ime_cursor_area_physical(x, y, width, height);
+    }
+
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        let _ = self.ime_sender
+            .lock()
+            .send(ImeRequest::Allowed(self.xwindow, allowed));
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId { WindowId(self.xwindow) }
 }
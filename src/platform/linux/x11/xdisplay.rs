@@ -1,6 +1,7 @@
 use std::ptr;
 use std::fmt;
 use std::error::Error;
+use std::time::Duration;
 
 use libc;
 use parking_lot::Mutex;
@@ -76,6 +77,23 @@ impl XConnection {
     pub fn ignore_error(&self) {
         *self.latest_error.lock() = None;
     }
+
+    /// Blocks until the server sends something, or `timeout` has passed. Returns false if it
+    /// timed out.
+    pub fn wait_for_events(&self, timeout: Option<Duration>) -> bool {
+        let mut poll_fd = libc::pollfd {
+            fd: unsafe { (self.xlib.XConnectionNumber)(self.display) },
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // Rounded up, so we don't wake up just before the deadline.
+        let timeout_ms = match timeout {
+            Some(timeout) => (timeout.as_secs() * 1000 + (timeout.subsec_nanos() as u64 + 999_999) / 1_000_000) as libc::c_int,
+            None => -1,
+        };
+        // Being interrupted by a signal isn't a timeout.
+        unsafe { libc::poll(&mut poll_fd, 1, timeout_ms) != 0 }
+    }
 }
 
 impl fmt::Debug for XConnection {
@@ -493,6 +493,12 @@ impl EventsLoop {
         }
     }
 
+    #[inline]
+    pub fn get_pointer_state(&self) -> Option<::PointerState> {
+        // TODO
+        None
+    }
+
     pub fn create_proxy(&self) -> Proxy {
         Proxy {}
     }
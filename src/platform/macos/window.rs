@@ -996,12 +996,24 @@ impl Window2 {
     }
 
     #[inline]
-    pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
-        // TODO: Do this for real https://stackoverflow.com/a/40922095/5435443
+    pub fn set_custom_cursor(&self, _cursor: ::CustomCursor) {
+        // TODO
+    }
+
+    #[inline]
+    pub fn set_cursor_grab(&self, mode: ::CursorGrabMode) -> Result<(), String> {
+        // TODO: Confine the cursor for real https://stackoverflow.com/a/40922095/5435443
+        let grab = mode != ::CursorGrabMode::None;
         CGDisplay::associate_mouse_and_mouse_cursor_position(!grab)
             .map_err(|status| format!("Failed to grab cursor: `CGError` {:?}", status))
     }
 
+    #[inline]
+    pub fn confine_cursor_to(&self, area: Option<(LogicalPosition, LogicalSize)>) -> Result<(), String> {
+        // TODO: Confine the cursor to the area, rather than the whole window.
+        self.set_cursor_grab(if area.is_some() { ::CursorGrabMode::Confined } else { ::CursorGrabMode::None })
+    }
+
     #[inline]
     pub fn hide_cursor(&self, hide: bool) {
         let cursor_class = Class::get("NSCursor").unwrap();
@@ -1024,6 +1036,12 @@ impl Window2 {
         }
     }
 
+    #[inline]
+    pub fn get_cursor_position(&self) -> Option<LogicalPosition> {
+        // TODO
+        None
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, cursor_position: LogicalPosition) -> Result<(), String> {
         let window_position = self.get_inner_position()
@@ -1149,6 +1167,16 @@ impl Window2 {
         set_ime_spot(*self.view, *self.input_context, logical_spot.x, logical_spot.y);
     }
 
+    #[inline]
+    pub fn set_ime_allowed(&self, _allowed: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_ime_cursor_area(&self, logical_position: LogicalPosition, _size: LogicalSize) {
+        self.set_ime_spot(logical_position);
+    }
+
     #[inline]
     pub fn get_current_monitor(&self) -> RootMonitorId {
         unsafe {
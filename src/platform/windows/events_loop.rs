@@ -45,6 +45,7 @@ use {
     KeyboardInput,
     LogicalPosition,
     LogicalSize,
+    MouseButton,
     PhysicalSize,
     WindowEvent,
     WindowId as SuperWindowId,
@@ -266,6 +267,12 @@ impl EventsLoop {
         }
     }
 
+    #[inline]
+    pub fn get_pointer_state(&self) -> Option<::PointerState> {
+        // TODO
+        None
+    }
+
     pub fn create_proxy(&self) -> EventsLoopProxy {
         EventsLoopProxy {
             thread_id: self.thread_id,
@@ -425,6 +432,15 @@ unsafe fn release_mouse() {
     });
 }
 
+/// Returns the extra mouse button of a `WM_XBUTTONDOWN` or `WM_XBUTTONUP` message.
+fn get_xbutton(wparam: WPARAM) -> MouseButton {
+    match winuser::GET_XBUTTON_WPARAM(wparam) {
+        winuser::XBUTTON1 => MouseButton::Back,
+        winuser::XBUTTON2 => MouseButton::Forward,
+        xbutton => MouseButton::Other(xbutton as u8),
+    }
+}
+
 /// Any window whose callback is configured to this function will have its events propagated
 /// through the events loop of the thread the window was created in.
 //
@@ -780,30 +796,28 @@ pub unsafe extern "system" fn callback(
 
         winuser::WM_XBUTTONDOWN => {
             use events::WindowEvent::MouseInput;
-            use events::MouseButton::Other;
             use events::ElementState::Pressed;
-            let xbutton = winuser::GET_XBUTTON_WPARAM(wparam);
+            let button = get_xbutton(wparam);
 
             capture_mouse(window);
 
             send_event(Event::WindowEvent {
                 window_id: SuperWindowId(WindowId(window)),
-                event: MouseInput { device_id: DEVICE_ID, state: Pressed, button: Other(xbutton as u8), modifiers: event::get_key_mods() }
+                event: MouseInput { device_id: DEVICE_ID, state: Pressed, button, modifiers: event::get_key_mods() }
             });
             0
         },
 
         winuser::WM_XBUTTONUP => {
             use events::WindowEvent::MouseInput;
-            use events::MouseButton::Other;
             use events::ElementState::Released;
-            let xbutton = winuser::GET_XBUTTON_WPARAM(wparam);
+            let button = get_xbutton(wparam);
 
             release_mouse();
 
             send_event(Event::WindowEvent {
                 window_id: SuperWindowId(WindowId(window)),
-                event: MouseInput { device_id: DEVICE_ID, state: Released, button: Other(xbutton as u8), modifiers: event::get_key_mods() }
+                event: MouseInput { device_id: DEVICE_ID, state: Released, button, modifiers: event::get_key_mods() }
             });
             0
         },
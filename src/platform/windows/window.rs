@@ -345,6 +345,11 @@ impl Window {
         cur.cursor = Cursor(cursor_id);
     }
 
+    #[inline]
+    pub fn set_custom_cursor(&self, _cursor: ::CustomCursor) {
+        // TODO
+    }
+
     unsafe fn cursor_is_grabbed(&self) -> Result<bool, String> {
         let mut client_rect: RECT = mem::uninitialized();
         let mut clip_rect: RECT = mem::uninitialized();
@@ -389,7 +394,9 @@ impl Window {
     }
 
     #[inline]
-    pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
+    pub fn set_cursor_grab(&self, mode: ::CursorGrabMode) -> Result<(), String> {
+        // TODO: Lock the cursor for real, rather than confining it.
+        let grab = mode != ::CursorGrabMode::None;
         let currently_grabbed = unsafe { self.cursor_is_grabbed() }?;
         let window_state = Arc::clone(&self.window_state);
         {
@@ -419,6 +426,12 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn confine_cursor_to(&self, area: Option<(LogicalPosition, LogicalSize)>) -> Result<(), String> {
+        // TODO: Confine the cursor to the area, rather than the whole window.
+        self.set_cursor_grab(if area.is_some() { ::CursorGrabMode::Confined } else { ::CursorGrabMode::None })
+    }
+
     #[inline]
     pub fn hide_cursor(&self, hide: bool) {
         let window_state = Arc::clone(&self.window_state);
@@ -441,6 +454,21 @@ impl Window {
         get_window_scale_factor(self.window.0, self.window.1)
     }
 
+    #[inline]
+    pub fn get_cursor_position(&self) -> Option<LogicalPosition> {
+        let mut point = POINT { x: 0, y: 0 };
+        unsafe {
+            if winuser::GetCursorPos(&mut point) == 0 {
+                return None;
+            }
+            if winuser::ScreenToClient(self.window.0, &mut point) == 0 {
+                return None;
+            }
+        }
+        let dpi_factor = self.get_hidpi_factor();
+        Some(LogicalPosition::from_physical((point.x, point.y), dpi_factor))
+    }
+
     fn set_cursor_position_physical(&self, x: i32, y: i32) -> Result<(), String> {
         let mut point = POINT { x, y };
         unsafe {
@@ -791,6 +819,16 @@ impl Window {
     pub fn set_ime_spot(&self, _logical_spot: LogicalPosition) {
         unimplemented!();
     }
+
+    #[inline]
+    pub fn set_ime_allowed(&self, _allowed: bool) {
+        // N/A
+    }
+
+    #[inline]
+    pub fn set_ime_cursor_area(&self, _position: LogicalPosition, _size: LogicalSize) {
+        // N/A
+    }
 }
 
 impl Drop for Window {
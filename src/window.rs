@@ -2,6 +2,8 @@ use std::collections::vec_deque::IntoIter as VecDequeIter;
 
 use {
     CreationError,
+    CursorGrabMode,
+    CustomCursor,
     EventsLoop,
     Icon,
     LogicalPosition,
@@ -319,11 +321,37 @@ impl Window {
 
     /// Modifies the mouse cursor of the window.
     /// Has no effect on Android.
+    ///
+    /// On Wayland, the cursor is loaded from the XCursor theme named by `XCURSOR_THEME`, at the
+    /// size in `XCURSOR_SIZE`.
     #[inline]
     pub fn set_cursor(&self, cursor: MouseCursor) {
         self.window.set_cursor(cursor);
     }
 
+    /// Replaces the mouse cursor of the window with an image, until `set_cursor` is called again.
+    ///
+    /// Cursors created with `CustomCursor::from_frames` are animated.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This only has an effect on X11 and Wayland.
+    #[inline]
+    pub fn set_custom_cursor(&self, cursor: CustomCursor) {
+        self.window.set_custom_cursor(cursor);
+    }
+
+    /// Returns the position of the cursor in window coordinates.
+    ///
+    /// ## Platform-specific
+    ///
+    /// On Wayland, this is `None` unless the cursor is over the window. Only implemented on
+    /// X11, Wayland and Windows; returns `None` elsewhere.
+    #[inline]
+    pub fn get_cursor_position(&self) -> Option<LogicalPosition> {
+        self.window.get_cursor_position()
+    }
+
     /// Changes the position of the cursor in window coordinates.
     #[inline]
     pub fn set_cursor_position(&self, position: LogicalPosition) -> Result<(), String> {
@@ -332,21 +360,57 @@ impl Window {
 
     /// Grabs the cursor, preventing it from leaving the window.
     ///
+    /// This is the same as `set_cursor_grab(CursorGrabMode::Confined)`, or `CursorGrabMode::None` to
+    /// release it.
+    #[inline]
+    pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
+        self.set_cursor_grab(if grab { CursorGrabMode::Confined } else { CursorGrabMode::None })
+    }
+
+    /// Sets how the cursor is grabbed by the window, either confining it to the window or locking it in
+    /// place, e.g. to turn its motion into camera movement.
+    ///
     /// ## Platform-specific
     ///
-    /// On macOS, this presently merely locks the cursor in a fixed location, which looks visually awkward.
+    /// On macOS, `Confined` presently merely locks the cursor in a fixed location, which looks visually
+    /// awkward.
+    ///
+    /// On Windows, `Locked` presently confines the cursor like `Confined` does.
+    ///
+    /// On X11, `Locked` warps the cursor back to where it was locked whenever it moves, without reporting
+    /// `CursorMoved`.
+    ///
+    /// On Wayland, this returns an error if the compositor doesn't support pointer constraints.
+    ///
+    /// On Emscripten, both modes lock the pointer.
     ///
     /// This has no effect on Android or iOS.
     #[inline]
-    pub fn grab_cursor(&self, grab: bool) -> Result<(), String> {
-        self.window.grab_cursor(grab)
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), String> {
+        self.window.set_cursor_grab(mode)
+    }
+
+    /// Confines the cursor to an area of the window, given in window coordinates, or clears the area with
+    /// `None`.
+    ///
+    /// This is the same as `set_cursor_grab(CursorGrabMode::Confined)`, only with a smaller area, which is
+    /// kept until it's cleared or `set_cursor_grab` is called. Clearing it goes back to the grab last set with
+    /// `set_cursor_grab`. The cursor is confined again when the window gets the focus back.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is only implemented on X11 and Wayland. Elsewhere, the cursor is grabbed as it is by
+    /// `set_cursor_grab(CursorGrabMode::Confined)`, and `None` releases it.
+    #[inline]
+    pub fn confine_cursor_to(&self, area: Option<(LogicalPosition, LogicalSize)>) -> Result<(), String> {
+        self.window.confine_cursor_to(area)
     }
 
     /// Hides the cursor, making it invisible but still usable.
     ///
     /// ## Platform-specific
     ///
-    /// On Windows and X11, the cursor is only hidden within the confines of the window.
+    /// On Windows, X11 and Wayland, the cursor is only hidden within the confines of the window.
     ///
     /// On macOS, the cursor is hidden as long as the window has input focus, even if the cursor is outside of the
     /// window.
@@ -400,6 +464,35 @@ impl Window {
         self.window.set_ime_spot(position)
     }
 
+    /// Sets the area of the text being edited, in client area coordinates relative to the top left.
+    ///
+    /// Unlike `set_ime_spot`, this lets the input method place its candidate box next to the whole
+    /// line instead of covering it. `position` is the top left corner of the area, which usually
+    /// covers the text cursor and the line it is on.
+    ///
+    /// ## Platform-specific
+    ///
+    /// On macOS, only `position` is used, like `set_ime_spot`. This has no effect on Windows, iOS,
+    /// Android and Emscripten.
+    #[inline]
+    pub fn set_ime_cursor_area(&self, position: LogicalPosition, size: LogicalSize) {
+        self.window.set_ime_cursor_area(position, size)
+    }
+
+    /// Sets whether the input method is enabled for this window.
+    ///
+    /// When disallowed, key presses reach the application directly instead of being intercepted
+    /// by the input method, which suits games and other windows without text fields. Input
+    /// methods are allowed by default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This only has an effect on X11 and Wayland.
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        self.window.set_ime_allowed(allowed)
+    }
+
     /// Returns the monitor on which the window currently resides
     #[inline]
     pub fn get_current_monitor(&self) -> MonitorId {